@@ -1,12 +1,26 @@
 use crate::imports::*;
 use super::*;
 use std::sync::{Arc, Mutex};
+use crate::dx_manager::services::{BridgeMetricsReport, BridgeNodeStatus, ReconfigureStatus};
+use chrono::{Local, Timelike};
+
+// hours covered by the blocks found chart
+const BLOCK_CHART_HOURS: usize = 24;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BridgeView {
+  Dashboard,
+  Console,
+}
 
 pub struct StratumBridge {
   lines: VecDeque<String>,
   max_lines: usize,
   receiver: Receiver<DaemonMessage>,
   font_size: f32,
+  view: BridgeView,
+  // last reconfigure status closed by the user
+  dismissed: Option<ReconfigureStatus>,
 }
 
 impl StratumBridge {
@@ -15,7 +29,9 @@ impl StratumBridge {
       lines: VecDeque::new(),
       max_lines: 1000,
       receiver,
-      font_size: 12.0
+      font_size: 12.0,
+      view: BridgeView::Dashboard,
+      dismissed: None,
     }
   }
 
@@ -43,6 +59,243 @@ impl StratumBridge {
       egui::Color32::GRAY
     }
   }
+
+  fn render_console(&mut self, ui: &mut egui::Ui) {
+    let available_height = ui.available_height();
+    let available_width = ui.available_width();
+    ui.horizontal(|ui| {
+      if ui.button(i18n("Clear Console")).clicked() {
+        self.lines.clear();
+      }
+
+      ui.label("Font size:");
+      ui.add(egui::Slider::new(&mut self.font_size, 8.0..=20.0));
+    });
+
+    let frame_height = available_height - ui.spacing().interact_size.y - 20.0;
+    let frame_width = available_width;
+
+    egui::Frame::none()
+      .fill(egui::Color32::BLACK)
+      .rounding(egui::Rounding::same(5.0))
+      .inner_margin(6.0)
+      .show(ui, |ui| {
+        egui::ScrollArea::vertical()
+          .max_height(frame_height)
+          .max_width(frame_width)
+          .stick_to_bottom(true)
+          .show(ui, |ui| {
+            for line in &self.lines {
+              ui.colored_label(
+                Self::get_color_for_line(line), 
+                egui::RichText::new(line).size(self.font_size)
+              );
+            }
+          });
+    });
+  }
+
+  fn render_reconfigure_status(&mut self, core: &mut Core, ui: &mut egui::Ui) {
+    let Some(status) = core.node_state().bridge_reconfigure().cloned() else {
+      return;
+    };
+    if status == ReconfigureStatus::Restarting {
+      self.dismissed = None;
+    } else if self.dismissed.as_ref() == Some(&status) {
+      return;
+    }
+
+    let color = match status {
+      ReconfigureStatus::Restarting => theme_color().default_color,
+      ReconfigureStatus::Applied => theme_color().strong_color,
+      ReconfigureStatus::Failed(_) => theme_color().error_color,
+    };
+    ui.horizontal(|ui| {
+      ui.label(RichText::new(status.to_string()).color(color));
+      if status != ReconfigureStatus::Restarting && ui.small_button(i18n("Dismiss")).clicked() {
+        self.dismissed = Some(status.clone());
+      }
+    });
+    ui.separator();
+  }
+
+  fn render_node_status(&mut self, core: &mut Core, ui: &mut egui::Ui) {
+    let Some(status) = core.node_state().bridge_node() else {
+      return;
+    };
+
+    let color = match status {
+      BridgeNodeStatus::Connected(_) => theme_color().strong_color,
+      BridgeNodeStatus::Unreachable { .. } => theme_color().error_color,
+    };
+    ui.label(RichText::new(status.to_string()).color(color));
+    ui.separator();
+  }
+
+  fn render_dashboard(&mut self, core: &mut Core, ui: &mut egui::Ui) {
+    let node_state = core.node_state();
+    let Some(report) = node_state.bridge_metrics() else {
+      ui.vertical_centered(|ui| {
+        ui.add_space(32.0);
+        ui.label(RichText::new(i18n("Waiting for the bridge metrics...")).color(theme_color().separator_color));
+      });
+      return;
+    };
+
+    if let Some(error) = report.error.as_ref() {
+      ui.label(RichText::new(error).color(theme_color().warning_color));
+      ui.add_space(4.0);
+    }
+
+    egui::ScrollArea::vertical()
+      .auto_shrink([false; 2])
+      .show(ui, |ui| {
+        egui::Grid::new("bridge_summary")
+          .num_columns(5)
+          .spacing([32.0, 4.0])
+          .show(ui, |ui| {
+            for label in [i18n("Hashrate"), i18n("Workers"), i18n("Accepted"), i18n("Stale / Invalid"), i18n("Blocks Found")] {
+              ui.label(RichText::new(label).color(theme_color().default_color));
+            }
+            ui.end_row();
+            for value in [
+              format_hashrate(report.hashrate() as u64),
+              report.workers.len().to_string(),
+              format_number(report.valid_shares()),
+              format!("{} / {}", format_number(report.stale_shares()), format_number(report.invalid_shares())),
+              format_number(report.total_blocks),
+            ] {
+              ui.label(RichText::new(value).size(20.0).color(theme_color().strong_color));
+            }
+            ui.end_row();
+          });
+
+        ui.add_space(8.0);
+        ui.label(RichText::new(i18n("Hashrate (last hour)")).strong());
+        Self::render_hashrate_chart(report, ui);
+
+        ui.add_space(8.0);
+        ui.label(RichText::new(i18n("Workers")).strong());
+        if report.workers.is_empty() {
+          ui.label(RichText::new(i18n("No miners connected")).color(theme_color().separator_color));
+        } else {
+          egui::Grid::new("bridge_workers")
+            .num_columns(7)
+            .striped(true)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+              for label in [i18n("Worker"), i18n("Hashrate"), i18n("Accepted"), i18n("Stale"), i18n("Invalid"), i18n("Difficulty"), i18n("Blocks")] {
+                ui.label(RichText::new(label).strong());
+              }
+              ui.end_row();
+
+              for worker in report.workers.iter() {
+                let name = if worker.worker.is_empty() { i18n("(unnamed)") } else { worker.worker.as_str() };
+                ui.label(name).on_hover_text(&worker.wallet);
+                ui.label(format_hashrate(worker.hashrate as u64));
+                ui.label(format_number(worker.valid_shares));
+                ui.label(format_number(worker.stale_shares));
+                ui.label(RichText::new(format_number(worker.invalid_shares)).color(
+                  if worker.invalid_shares > 0 { theme_color().warning_color } else { theme_color().default_color }
+                ));
+                ui.label(worker.difficulty.map(|difficulty| format_diff(difficulty.round() as u64)).unwrap_or_else(|| "-".to_string()));
+                ui.label(format_number(worker.blocks));
+                ui.end_row();
+              }
+            });
+        }
+
+        ui.add_space(8.0);
+        ui.label(RichText::new(i18n("Blocks found (last 24 hours)")).strong());
+        Self::render_blocks_chart(report, ui);
+
+        if !report.blocks.is_empty() {
+          ui.add_space(4.0);
+          CollapsingHeader::new(i18n("Recent Blocks"))
+            .default_open(false)
+            .show(ui, |ui| {
+              for block in report.blocks.iter() {
+                let found = block
+                  .found
+                  .map(|found| found.format("%Y-%m-%d %H:%M:%S").to_string())
+                  .unwrap_or_else(|| i18n("before monitoring").to_string());
+                ui.label(RichText::new(format!("{} \u{2022} {} \u{2022} {} {} \u{2022} {}", found, block.worker, i18n("blue score"), block.blue_score, block.hash)).monospace());
+              }
+            });
+        }
+      });
+  }
+
+  fn render_hashrate_chart(report: &BridgeMetricsReport, ui: &mut egui::Ui) {
+    let (rect, _) = ui.allocate_exact_size(vec2(ui.available_width(), 100.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, egui::Rounding::same(5.0), theme_color().button_color);
+
+    let history = &report.hashrate_history;
+    let max = history.iter().cloned().fold(0.0, f64::max);
+    if history.len() < 2 || max <= 0.0 {
+      return;
+    }
+
+    let step = rect.width() / (history.len() - 1) as f32;
+    let points = history
+      .iter()
+      .enumerate()
+      .map(|(index, hashrate)| {
+        egui::pos2(rect.left() + index as f32 * step, rect.bottom() - (hashrate / max) as f32 * (rect.height() - 8.0))
+      })
+      .collect::<Vec<_>>();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(2.0, theme_color().strong_color)));
+    painter.text(
+      rect.left_top() + vec2(6.0, 4.0),
+      Align2::LEFT_TOP,
+      format_hashrate(max as u64),
+      egui::FontId::proportional(12.0),
+      theme_color().default_color,
+    );
+  }
+
+  fn render_blocks_chart(report: &BridgeMetricsReport, ui: &mut egui::Ui) {
+    let now = Local::now();
+    let mut buckets = [0u32; BLOCK_CHART_HOURS];
+    for found in report.blocks.iter().filter_map(|block| block.found) {
+      let hours = (now - found).num_hours();
+      if (0..BLOCK_CHART_HOURS as i64).contains(&hours) {
+        buckets[BLOCK_CHART_HOURS - 1 - hours as usize] += 1;
+      }
+    }
+
+    let (rect, _) = ui.allocate_exact_size(vec2(ui.available_width(), 80.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, egui::Rounding::same(5.0), theme_color().button_color);
+
+    let max = buckets.iter().cloned().max().unwrap_or(0).max(1);
+    let width = rect.width() / BLOCK_CHART_HOURS as f32;
+    for (index, count) in buckets.iter().enumerate() {
+      let left = rect.left() + index as f32 * width;
+      if *count > 0 {
+        let height = *count as f32 / max as f32 * (rect.height() - 18.0);
+        let bar = egui::Rect::from_min_max(
+          egui::pos2(left + 2.0, rect.bottom() - 14.0 - height),
+          egui::pos2(left + width - 2.0, rect.bottom() - 14.0),
+        );
+        painter.rect_filled(bar, egui::Rounding::same(2.0), theme_color().strong_color);
+        painter.text(bar.center_top() - vec2(0.0, 2.0), Align2::CENTER_BOTTOM, count.to_string(), egui::FontId::proportional(10.0), theme_color().default_color);
+      }
+      // label every sixth hour with the hour of day
+      let hours_ago = (BLOCK_CHART_HOURS - 1 - index) as i64;
+      if hours_ago % 6 == 0 {
+        let hour = (now - chrono::Duration::hours(hours_ago)).hour();
+        painter.text(
+          egui::pos2(left + width / 2.0, rect.bottom() - 2.0),
+          Align2::CENTER_BOTTOM,
+          format!("{hour:02}h"),
+          egui::FontId::proportional(10.0),
+          theme_color().separator_color,
+        );
+      }
+    }
+  }
 }
 
 impl ComponentT for StratumBridge {
@@ -62,38 +315,19 @@ impl ComponentT for StratumBridge {
     egui::Frame::none()
       .inner_margin(10.0)
       .show(ui, |ui| {
-        let available_height = ui.available_height();
-        let available_width = ui.available_width();
         ui.horizontal(|ui| {
-          if ui.button(i18n("Clear Console")).clicked() {
-            self.lines.clear();
-          }
-
-          ui.label("Font size:");
-          ui.add(egui::Slider::new(&mut self.font_size, 8.0..=20.0));
+          ui.selectable_value(&mut self.view, BridgeView::Dashboard, i18n("Dashboard"));
+          ui.selectable_value(&mut self.view, BridgeView::Console, i18n("Console"));
         });
+        ui.separator();
 
-        let frame_height = available_height - ui.spacing().interact_size.y - 20.0;
-        let frame_width = available_width;
+        self.render_node_status(core, ui);
+        self.render_reconfigure_status(core, ui);
 
-        egui::Frame::none()
-          .fill(egui::Color32::BLACK)
-          .rounding(egui::Rounding::same(5.0))
-          .inner_margin(6.0)
-          .show(ui, |ui| {
-            egui::ScrollArea::vertical()
-              .max_height(frame_height)
-              .max_width(frame_width)
-              .stick_to_bottom(true)
-              .show(ui, |ui| {
-                for line in &self.lines {
-                  ui.colored_label(
-                    Self::get_color_for_line(line), 
-                    egui::RichText::new(line).size(self.font_size)
-                  );
-                }
-              });
-        });
+        match self.view {
+          BridgeView::Dashboard => self.render_dashboard(core, ui),
+          BridgeView::Console => self.render_console(ui),
+        }
     });
   }
 }
@@ -31,6 +31,31 @@ impl DaemonConsole {
     }
   }
 
+  #[cfg(not(target_arch = "wasm32"))]
+  fn render_disk_space(core: &mut Core, ui: &mut egui::Ui) {
+    use crate::storage::format_size;
+
+    let Some(disk) = core.storage.disk_space() else {
+      return;
+    };
+
+    ui.separator();
+    let folder = core.storage.folder_size().map(format_size).unwrap_or_else(|| "...".to_string());
+    let text = format!(
+      "{} {} - {} {} / {}",
+      i18n("Data:"), folder,
+      i18n("Free:"), format_size(disk.available), format_size(disk.total),
+    );
+    let node_state = core.node_state();
+    let color = if node_state.is_disk_space_low() { theme_color().warning_color } else { theme_color().default_color };
+    ui.label(RichText::new(text).color(color));
+
+    let recovered = disk.available >= core.settings.node.disk_space_stop_gb * 1_000_000_000;
+    if node_state.is_disk_space_halted() && recovered && ui.button(i18n("Restart Node")).clicked() {
+      core.resume_after_disk_space();
+    }
+  }
+
   fn get_color_for_line(line: &str) -> egui::Color32 {
     if line.contains("[ERROR]") {
       egui::Color32::RED
@@ -51,7 +76,7 @@ impl ComponentT for DaemonConsole {
 
   fn render(
     &mut self,
-    _core: &mut Core,
+    core: &mut Core,
     ctx: &egui::Context,
     _frame: &mut eframe::Frame,
     ui: &mut egui::Ui,
@@ -70,6 +95,9 @@ impl ComponentT for DaemonConsole {
 
           ui.label("Font size:");
           ui.add(egui::Slider::new(&mut self.font_size, 8.0..=20.0));
+
+          #[cfg(not(target_arch = "wasm32"))]
+          Self::render_disk_space(core, ui);
         });
 
         let frame_height = available_height - ui.spacing().interact_size.y - 20.0;
@@ -97,7 +97,7 @@ impl Footer {
         .on_hover_cursor(egui::CursorIcon::Default)
       ;
 
-      ui.add_sized(
+      let status_response = ui.add_sized(
         [desired_width - icon_size, footer_height],
         egui::Label::new(
           egui::RichText::new(status_message).color(status_color),
@@ -106,6 +106,14 @@ impl Footer {
         .on_hover_cursor(egui::CursorIcon::Default)
       ;
 
+      let hover = [describe_health(&core.node_state()), describe_daemon_crashes(&core.node_state())]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+      if !hover.is_empty() {
+        status_response.on_hover_text(hover.join("\n"));
+      }
+
       ui.separator();
 
       ui.add_sized(
@@ -127,6 +135,24 @@ impl Footer {
       )
         .on_hover_cursor(egui::CursorIcon::Default)
       ;
+
+      let node_state = core.node_state();
+      let progress = node_state.sync_progress();
+      if progress.is_syncing() {
+        ui.separator();
+        ui.add_space(4.0);
+
+        let mut bar = egui::ProgressBar::new(progress.fraction())
+          .desired_width(160.0)
+          .show_percentage();
+        if let Some(eta) = progress.eta() {
+          bar = bar.text(format!("{}% - {}", progress.progress.unwrap_or(0), format_duration(eta)));
+        }
+
+        if let Some(description) = progress.describe() {
+          ui.add(bar).on_hover_text(description);
+        }
+      }
     });
   }
 
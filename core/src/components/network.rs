@@ -53,6 +53,90 @@ fn show_stat(name:&str, text: String, pos: Vec2, ui: &mut egui::Ui) {
   );
 }
 
+fn show_sync_progress(progress: &crate::node_log::SyncProgress, x_area: f32, ui: &mut egui::Ui) {
+  let Some(description) = progress.describe() else {
+    return;
+  };
+
+  ui.vertical_centered(|ui| {
+    ui.add(
+      egui::ProgressBar::new(progress.fraction())
+        .desired_width(x_area * 0.75)
+        .text(description)
+    );
+
+    let mut details = format!(
+      "{}: {}  {}: {}",
+      i18n("Headers processed"),
+      format_number(progress.headers_processed),
+      i18n("Blocks processed"),
+      format_number(progress.blocks_processed),
+    );
+    if let Some((success, _)) = &progress.upnp {
+      details.push_str(&format!("  UPnP: {}", if *success { i18n("active") } else { i18n("failed") }));
+    }
+    let response = ui.label(RichText::new(details).color(theme_color().separator_color));
+    if let Some((_, message)) = &progress.upnp {
+      response.on_hover_text(message);
+    }
+
+    if let Some(error) = &progress.last_error {
+      ui.label(RichText::new(error).color(theme_color().error_color));
+    }
+  });
+}
+
+fn show_remote_endpoints(report: &crate::dx_manager::services::FailoverReport, ui: &mut egui::Ui) {
+  if report.endpoints.len() < 2 {
+    return;
+  }
+
+  ui.vertical_centered(|ui| {
+    CollapsingHeader::new(i18n("Remote Endpoints"))
+      .default_open(false)
+      .show(ui, |ui| {
+        for status in report.endpoints.iter() {
+          let active = report.active.as_ref() == Some(&status.url);
+          let color = if status.health.is_healthy() {
+            theme_color().strong_color
+          } else if status.checked.is_none() {
+            theme_color().separator_color
+          } else {
+            theme_color().warning_color
+          };
+
+          ui.horizontal(|ui| {
+            ui.label(RichText::new(if active { "\u{25CF}" } else { "\u{25CB}" }).color(color));
+            ui.label(RichText::new(&status.url).strong());
+            let mut text = status.health.to_string();
+            if let Some(checked) = status.checked {
+              text.push_str(&format!(" ({} {})", format_duration(checked.elapsed()), i18n("ago")));
+            }
+            ui.label(RichText::new(text).color(color));
+          });
+        }
+
+        if !report.history.is_empty() {
+          ui.add_space(4.0);
+          ui.label(RichText::new(i18n("Failover History")).strong());
+          for record in report.history.iter() {
+            ui.label(
+              RichText::new(format!(
+                "{} {}: {} \u{2192} {} ({})",
+                format_duration(record.time.elapsed()),
+                i18n("ago"),
+                record.from.clone().unwrap_or_else(|| "N/A".to_string()),
+                record.to,
+                record.reason,
+              ))
+              .color(theme_color().separator_color),
+            );
+          }
+        }
+      });
+  });
+}
+
 impl ComponentT for NetworkInfo {
   fn name(&self) -> Option<&'static str> {
     Some("Network Info")
@@ -113,7 +197,7 @@ impl ComponentT for NetworkInfo {
 
     let x_area = ui.available_width();
 
-    large_text(i18n("Metrics - Mainnet").to_string(), vec2(x_area / 2.0, 10.0), Align2::CENTER_TOP, theme_color().default_color, ui);
+    large_text(format!("{} - {}", i18n("Metrics"), core.settings.node.network.name()), vec2(x_area / 2.0, 10.0), Align2::CENTER_TOP, theme_color().default_color, ui);
     ui.painter().text(
       ui.available_rect_before_wrap().min + vec2(x_area / 2.0, 75.0),
       Align2::CENTER_TOP,
@@ -197,6 +281,22 @@ impl ComponentT for NetworkInfo {
         ui
       );
     });
+
+    ui.add_space(100.0);
+    show_sync_progress(core.node_state().sync_progress(), x_area, ui);
+
+    if let Some(report) = core.node_state().remote_endpoints() {
+      show_remote_endpoints(report, ui);
+    }
+
+    if core.settings.node.uses_resolver() {
+      ui.vertical_centered(|ui| {
+        ui.label(RichText::new(i18n("Node selected by the wRPC resolver")).color(theme_color().separator_color));
+        if ui.button(i18n("Re-resolve")).clicked() {
+          manager().waglayla_service().reconnect_rpc();
+        }
+      });
+    }
   });
   }
 }
\ No newline at end of file
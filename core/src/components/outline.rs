@@ -527,14 +527,17 @@ impl Outline {
   fn available_tabs(&self, core: &Core) -> Vec<Tab> {
     let mut tabs = vec![Tab::Wallet];
 
-    if core.settings.node.node_kind == WagLayladNodeKind::IntegratedAsDaemon {
-      if core.settings.node.enable_bridge
-      {
-        #[cfg(not(target_arch = "wasm32"))]
-        tabs.push(Tab::WalaBridge);
+    // the bridge mines against a remote node when no local node runs
+    #[cfg(not(target_arch = "wasm32"))]
+    if core.settings.node.enable_bridge {
+      tabs.push(Tab::WalaBridge);
+    }
+
+    if core.settings.node.node_kind.is_local() {
+      // the console relays daemon stdout, which the in-process node does not have
+      if matches!(core.settings.node.node_kind, WagLayladNodeKind::IntegratedAsDaemon | WagLayladNodeKind::ExternalAsDaemon) {
+        tabs.push(Tab::WalaNode);
       }
-      
-      tabs.push(Tab::WalaNode);
     }
 
     tabs.push(Tab::NetworkInfo);
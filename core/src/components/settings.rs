@@ -7,6 +7,7 @@ pub struct Settings {
   wrpc_borsh_network_interface : NetworkInterfaceEditor,
   wrpc_json_network_interface : NetworkInterfaceEditor,
   grpc_network_interface : NetworkInterfaceEditor,
+  bridge_settings : BridgeSettingsEditor,
   reset_settings : bool,
 }
 
@@ -18,13 +19,18 @@ impl Settings {
       wrpc_borsh_network_interface : NetworkInterfaceEditor::default(),
       wrpc_json_network_interface : NetworkInterfaceEditor::default(),
       grpc_network_interface : NetworkInterfaceEditor::default(),
+      bridge_settings : BridgeSettingsEditor::default(),
       reset_settings : false,
     }
   }
 
   pub fn load(&mut self, settings : crate::settings::Settings) {
     self.settings = settings;
+    self.reset_network_interfaces();
+    self.bridge_settings = BridgeSettingsEditor::from(&self.settings.bridge);
+  }
 
+  fn reset_network_interfaces(&mut self) {
     self.wrpc_borsh_network_interface = NetworkInterfaceEditor::from(&self.settings.node.wrpc_borsh_network_interface);
     self.wrpc_json_network_interface = NetworkInterfaceEditor::from(&self.settings.node.wrpc_json_network_interface);
     self.grpc_network_interface = NetworkInterfaceEditor::from(&self.settings.node.grpc_network_interface);
@@ -77,12 +83,86 @@ impl Settings {
               node_settings_error = Some(i18n("Data storage folder not found"));
             }
           }
+          ui.add_space(4.);
+          ui.horizontal(|ui| {
+            ui.label(i18n("Warn when free disk space is below (GB):"));
+            ui.add(egui::DragValue::new(&mut settings.disk_space_warning_gb).range(1..=10_000));
+          });
+          ui.horizontal(|ui| {
+            ui.label(i18n("Stop the node when free disk space is below (GB):"));
+            ui.add(egui::DragValue::new(&mut settings.disk_space_stop_gb).range(1..=10_000));
+          });
+          if settings.disk_space_stop_gb >= settings.disk_space_warning_gb {
+            ui.colored_label(theme_color().error_color, i18n("The stop threshold must be lower than the warning threshold"));
+            node_settings_error = Some(i18n("Invalid disk space thresholds"));
+          }
         });
     }
     node_settings_error
   }
 
-  pub fn render_remote_settings(_core: &mut Core, ui: &mut Ui, settings : &mut NodeSettings) -> Option<&'static str> {
+  #[cfg(not(target_arch = "wasm32"))]
+  fn render_resource_settings(core: &Core, ui: &mut Ui, settings: &mut NodeSettings) {
+    use crate::storage::format_size;
+
+    egui::Grid::new("node_resource_settings")
+      .num_columns(2)
+      .spacing([16.0, 4.0])
+      .show(ui, |ui| {
+        ui.label(i18n("Memory Scale"));
+        ui.add(egui::DragValue::new(&mut settings.ram_scale).range(0.1..=10.0).speed(0.05).fixed_decimals(2))
+          .on_hover_text_at_pointer(i18n("Scales the node caches. Lower values reduce memory usage at the cost of performance."));
+        ui.end_row();
+
+        ui.label(i18n("Outbound Peers"));
+        ui.add(egui::DragValue::new(&mut settings.outbound_target).range(1..=64));
+        ui.end_row();
+
+        ui.label(i18n("Inbound Peer Limit"));
+        ui.add(egui::DragValue::new(&mut settings.inbound_limit).range(0..=1024));
+        ui.end_row();
+
+        ui.label(i18n("RPC Max Clients"));
+        ui.add(egui::DragValue::new(&mut settings.rpc_max_clients).range(1..=1024));
+        ui.end_row();
+      });
+
+    ui.checkbox(&mut settings.archival, i18n("Archival node"))
+      .on_hover_text_at_pointer(i18n("Keep the full block history instead of pruning it. Requires significantly more disk space."));
+
+    ui.add_space(4.);
+    let memory = settings.estimated_memory_usage();
+    let label = format!("{} ~{}", i18n("Estimated memory usage:"), format_size(memory));
+    match total_memory() {
+      Some(total) if memory > total => {
+        ui.colored_label(theme_color().warning_color, label);
+        ui.colored_label(theme_color().warning_color, format!("{} {}", i18n("This exceeds the installed memory of"), format_size(total)));
+      }
+      _ => {
+        ui.label(label);
+      }
+    }
+
+    match settings.estimated_disk_usage() {
+      Some(disk) => {
+        let label = format!("{} ~{}", i18n("Estimated disk usage:"), format_size(disk));
+        match core.storage.disk_space() {
+          Some(space) if disk > space.available + core.storage.folder_size().unwrap_or_default() => {
+            ui.colored_label(theme_color().warning_color, label);
+            ui.colored_label(theme_color().warning_color, i18n("The data storage volume does not have enough free space"));
+          }
+          _ => {
+            ui.label(label);
+          }
+        }
+      }
+      None => {
+        ui.colored_label(theme_color().warning_color, i18n("Archival nodes grow continuously and require a large data volume"));
+      }
+    }
+  }
+
+  pub fn render_remote_settings(core: &mut Core, ui: &mut Ui, settings : &mut NodeSettings) -> Option<&'static str> {
     let mut node_settings_error = None;
 
     CollapsingHeader::new(i18n("Remote p2p Node Configuration"))
@@ -114,25 +194,266 @@ impl Settings {
                 ui.add(TextEdit::singleline(&mut settings.wrpc_url));
               });
 
-              if let Err(err) = WaglaylaRpcClient::parse_url(settings.wrpc_url.clone(), settings.wrpc_encoding, Network::Mainnet.into()) {
-                ui.label(
-                  RichText::new(err.to_string())
-                    .color(theme_color().warning_color),
-                );
-                node_settings_error = Some(i18n("Invalid wRPC URL"));
+              ui.label(
+                RichText::new(i18n("Accepts a host, host:port or a full ws:// or wss:// url with an optional path, e.g. wss://node.example.com/wrpc"))
+                  .color(theme_color().separator_color),
+              );
+
+              match WaglaylaRpcClient::parse_url(settings.wrpc_url.clone(), settings.wrpc_encoding, settings.network.into()) {
+                Ok(url) => {
+                  // shows the default port of the selected encoding when none is given
+                  ui.label(
+                    RichText::new(format!("{} {}", i18n("Connecting to"), url))
+                      .color(theme_color().separator_color),
+                  );
+
+                  if url.starts_with("wss://") {
+                    if let Some(error) = Self::render_tls_settings(ui, settings) {
+                      node_settings_error = Some(error);
+                    }
+                  }
+                }
+                Err(err) => {
+                  ui.label(
+                    RichText::new(err.to_string())
+                      .color(theme_color().warning_color),
+                  );
+                  node_settings_error = Some(i18n("Invalid wRPC URL"));
+                }
+              }
+            });
+
+          CollapsingHeader::new(i18n("Fallback Endpoints"))
+            .default_open(!settings.wrpc_fallback_urls.is_empty())
+            .show(ui, |ui| {
+              ui.label(
+                RichText::new(i18n("When the active node disconnects or falls behind, the wallet switches to the first healthy endpoint in this list."))
+                  .color(theme_color().separator_color),
+              );
+
+              let mut remove = None;
+              let mut raise = None;
+              let count = settings.wrpc_fallback_urls.len();
+              for (index, url) in settings.wrpc_fallback_urls.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                  ui.label(format!("{}.", index + 1));
+                  ui.add(TextEdit::singleline(url));
+                  if ui.add_enabled(index > 0, Button::new(i18n("Move Up"))).clicked() {
+                    raise = Some(index);
+                  }
+                  if ui.button(i18n("Remove")).clicked() {
+                    remove = Some(index);
+                  }
+                });
+
+                if let Err(err) = WaglaylaRpcClient::parse_url(url.trim().to_string(), settings.wrpc_encoding, settings.network.into()) {
+                  ui.colored_label(theme_color().warning_color, err.to_string());
+                  node_settings_error = Some(i18n("Invalid fallback wRPC URL"));
+                }
+              }
+
+              if let Some(index) = raise {
+                settings.wrpc_fallback_urls.swap(index - 1, index);
+              }
+              if let Some(index) = remove {
+                settings.wrpc_fallback_urls.remove(index);
+              }
+              if count < 16 && ui.button(i18n("Add Endpoint")).clicked() {
+                settings.wrpc_fallback_urls.push(String::new());
+              }
+            });
+
+          #[cfg(not(target_arch = "wasm32"))]
+          CollapsingHeader::new(i18n("Connection Proxy"))
+            .default_open(settings.wrpc_proxy_override)
+            .show(ui, |ui| {
+              ui.checkbox(&mut settings.wrpc_proxy_override, i18n("Use a different proxy for this connection"));
+              if settings.wrpc_proxy_override {
+                if let Some(error) = Self::render_proxy_settings(ui, "node_wrpc_proxy_settings", &mut settings.wrpc_proxy) {
+                  node_settings_error = Some(error);
+                }
               }
             });
           },
+          NodeConnectionConfigKind::Resolver => {
+            CollapsingHeader::new(i18n("Resolver Settings"))
+              .default_open(true)
+              .show(ui, |ui| {
+                ui.label(
+                  RichText::new(i18n("The resolver selects a public node on each connection. Leave the list empty to use the default resolvers."))
+                    .color(theme_color().separator_color),
+                );
+
+                ui.horizontal(|ui|{
+                  ui.label(i18n("wRPC Encoding:"));
+                  WrpcEncoding::iter().for_each(|encoding| {
+                    ui.radio_value(&mut settings.wrpc_encoding, *encoding, encoding.to_string());
+                  });
+                });
+
+                if settings.proxy.enable {
+                  ui.colored_label(theme_color().warning_color, i18n("Resolver connections can not be routed through the proxy. Disable the proxy or select another connection type."));
+                  node_settings_error = Some(i18n("The resolver does not support a proxy"));
+                }
+
+                let mut remove = None;
+                for (index, url) in settings.resolver_urls.iter_mut().enumerate() {
+                  ui.horizontal(|ui| {
+                    ui.label(i18n("Resolver URL:"));
+                    ui.add(TextEdit::singleline(url));
+                    if ui.button(i18n("Remove")).clicked() {
+                      remove = Some(index);
+                    }
+                  });
+
+                  let url = url.trim();
+                  if !url.is_empty() && !(url.starts_with("http://") || url.starts_with("https://")) {
+                    ui.colored_label(theme_color().warning_color, i18n("Resolver URLs must start with http:// or https://"));
+                    node_settings_error = Some(i18n("Invalid resolver URL"));
+                  }
+                }
+                if let Some(index) = remove {
+                  settings.resolver_urls.remove(index);
+                }
+                if ui.button(i18n("Add Resolver")).clicked() {
+                  settings.resolver_urls.push(String::new());
+                }
+
+                // only meaningful while the resolver configuration is the active one
+                if core.settings.node.uses_resolver() {
+                  ui.add_space(4.);
+                  let node_state = core.node_state();
+                  ui.horizontal(|ui| {
+                    ui.label(i18n("Selected node:"));
+                    ui.label(RichText::new(node_state.url().clone().unwrap_or_else(|| i18n("resolving...").to_string())).strong());
+                  });
+                  if ui.button(i18n("Re-resolve")).on_hover_text_at_pointer(i18n("Disconnect and let the resolver select another node")).clicked() {
+                    manager().waglayla_service().reconnect_rpc();
+                  }
+                }
+              });
+          },
           NodeConnectionConfigKind::PublicServerCustom => {
           },
           NodeConnectionConfigKind::PublicServerRandom => {
             ui.label(i18n("A random node will be selected on startup"));
+            if settings.proxy.enable && !settings.proxy.remote_dns {
+              ui.colored_label(theme_color().warning_color, i18n("Seeder lookups use the local DNS resolver. Enable remote DNS to resolve them through the proxy."));
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if core.settings.node.node_kind == WagLayladNodeKind::Remote
+              && core.settings.node.connection_config_kind == NodeConnectionConfigKind::PublicServerRandom
+            {
+              if let Some(status) = core.node_state().discovery() {
+                ui.label(RichText::new(status.to_string()).color(theme_color().separator_color));
+              }
+              if ui.button(i18n("Rediscover")).on_hover_text_at_pointer(i18n("Probe the public nodes again and connect to the best one")).clicked() {
+                manager().waglayla_service().update_services(&core.settings.node, None);
+              }
+            }
           },
         }
       });
 
+    #[cfg(not(target_arch = "wasm32"))]
+    CollapsingHeader::new(i18n("Proxy"))
+      .default_open(settings.proxy.enable)
+      .show(ui, |ui| {
+        ui.label(
+          RichText::new(i18n("Routes connections to remote nodes, including public node discovery and the endpoint monitors, through a SOCKS5 proxy such as Tor."))
+            .color(theme_color().separator_color),
+        );
+        if let Some(error) = Self::render_proxy_settings(ui, "node_proxy_settings", &mut settings.proxy) {
+          node_settings_error = Some(error);
+        }
+      });
+
     node_settings_error
   }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  fn render_proxy_settings(ui: &mut Ui, id: &str, proxy : &mut ProxySettings) -> Option<&'static str> {
+    ui.checkbox(&mut proxy.enable, i18n("Connect through a SOCKS5 proxy"));
+    if !proxy.enable {
+      return None;
+    }
+
+    egui::Grid::new(id)
+      .num_columns(2)
+      .spacing([8.0, 4.0])
+      .show(ui, |ui| {
+        ui.label(i18n("Proxy Address:"));
+        ui.add(TextEdit::singleline(&mut proxy.address).hint_text("127.0.0.1:9050"));
+        ui.end_row();
+
+        ui.label(i18n("Username:"));
+        ui.add(TextEdit::singleline(&mut proxy.username).hint_text(i18n("optional")));
+        ui.end_row();
+
+        ui.label(i18n("Password:"));
+        ui.add(TextEdit::singleline(&mut proxy.password).password(true));
+        ui.end_row();
+      });
+
+    ui.checkbox(&mut proxy.remote_dns, i18n("Resolve host names through the proxy"))
+      .on_hover_text_at_pointer(i18n("Prevents DNS lookups of node and seeder names from leaking outside of the proxy"));
+
+    match proxy.validate() {
+      Ok(()) => None,
+      Err(error) => {
+        ui.colored_label(theme_color().warning_color, error);
+        Some(i18n("Invalid proxy settings"))
+      }
+    }
+  }
+
+  fn render_tls_settings(ui: &mut Ui, settings : &mut NodeSettings) -> Option<&'static str> {
+    let mut error = None;
+
+    CollapsingHeader::new(i18n("TLS Settings"))
+      .default_open(settings.tls_trust().is_custom())
+      .show(ui, |ui| {
+        ui.label(
+          RichText::new(i18n("By default the server certificate is verified against the system certificates. Nodes behind a private CA or a self-signed certificate need the CA certificate or a pinned fingerprint. Applies to the fallback endpoints as well."))
+            .color(theme_color().separator_color),
+        );
+
+        egui::Grid::new("node_tls_settings")
+          .num_columns(2)
+          .spacing([8.0, 4.0])
+          .show(ui, |ui| {
+            ui.label(i18n("CA Certificate (PEM):"));
+            ui.add(TextEdit::singleline(&mut settings.wrpc_tls_ca_file).hint_text(i18n("optional path")));
+            ui.end_row();
+
+            ui.label(i18n("SHA-256 Fingerprint:"));
+            ui.add(TextEdit::singleline(&mut settings.wrpc_tls_fingerprint).hint_text(i18n("optional, e.g. AB:CD:...")));
+            ui.end_row();
+          });
+
+        let trust = settings.tls_trust();
+        if !trust.ca_file.is_empty() && !std::path::Path::new(&trust.ca_file).is_file() {
+          ui.colored_label(theme_color().warning_color, i18n("The CA certificate file does not exist"));
+          error = Some(i18n("Invalid CA certificate file"));
+        }
+        match trust.fingerprint() {
+          Ok(Some(_)) if trust.ca_file.is_empty() => {
+            ui.label(
+              RichText::new(i18n("Only a certificate with this fingerprint is accepted, the certificate chain is not verified."))
+                .color(theme_color().separator_color),
+            );
+          }
+          Err(err) => {
+            ui.colored_label(theme_color().warning_color, err.to_string());
+            error = Some(i18n("Invalid certificate fingerprint"));
+          }
+          _ => {}
+        }
+      });
+
+    error
+  }
 }
 
 impl ComponentT for Settings {
@@ -197,14 +518,78 @@ impl Settings {
                 });
               });
 
+              #[cfg(not(target_arch = "wasm32"))]
+              let has_network = self.settings.node.node_kind != WagLayladNodeKind::Disabled;
+              #[cfg(target_arch = "wasm32")]
+              let has_network = true;
+
+              if has_network {
+                ui.horizontal_wrapped(|ui|{
+                  ui.label(i18n("Network:"));
+                  Network::iter().for_each(|network| {
+                    ui.radio_value(&mut self.settings.node.network, *network, network.name())
+                      .on_hover_text_at_pointer(network.describe());
+                  });
+                });
+              }
+
+              #[cfg(not(target_arch = "wasm32"))]
+              CollapsingHeader::new(i18n("Stratum Bridge"))
+                .default_open(true)
+                .show(ui, |ui| {
+                  ui.horizontal(|ui| {
+                    let response = ui.add(toggle(&mut self.settings.node.enable_bridge));
+                  });
+                  if !self.settings.node.node_kind.is_local() {
+                    ui.label(i18n("Without a local node the bridge mines against the remote node set in its configuration."));
+                  }
+                });
+
+              #[cfg(not(target_arch = "wasm32"))]
+              if self.settings.node.node_kind == WagLayladNodeKind::ExternalAsDaemon {
+                CollapsingHeader::new(i18n("External Node Binary"))
+                  .default_open(true)
+                  .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                      ui.label(i18n("waglaylad binary:"));
+                      ui.add(TextEdit::singleline(&mut self.settings.node.waglaylad_daemon_binary));
+                    });
+
+                    let binary = self.settings.node.waglaylad_daemon_binary.trim();
+                    if binary.is_empty() {
+                      node_settings_error = Some(i18n("Please specify the path to the waglaylad binary"));
+                    } else if !Path::new(binary).is_file() {
+                      ui.colored_label(theme_color().error_color, i18n("waglaylad binary not found at"));
+                      ui.label(format!("\"{}\"", binary));
+                      node_settings_error = Some(i18n("waglaylad binary not found"));
+                    } else if let Some(version) = core.node_state().daemon_version() {
+                      ui.label(format!("{} {}", i18n("Running version:"), version));
+                    }
+                  });
+              }
+
               match self.settings.node.node_kind {
                 WagLayladNodeKind::Remote => {},
-                WagLayladNodeKind::IntegratedAsDaemon => {
-                  CollapsingHeader::new(i18n("Stratum Bridge"))
-                    .default_open(true)
+                WagLayladNodeKind::IntegratedAsDaemon | WagLayladNodeKind::ExternalAsDaemon => {
+                  CollapsingHeader::new(i18n("Node Supervisor"))
+                    .default_open(false)
                     .show(ui, |ui| {
                       ui.horizontal(|ui| {
-                        let response = ui.add(toggle(&mut self.settings.node.enable_bridge));
+                        ui.add(toggle(&mut self.settings.node.waglaylad_daemon_restart_enable))
+                          .on_hover_text_at_pointer(i18n("Restart the node automatically if it exits unexpectedly."));
+                        ui.label(i18n("Restart node after a crash"));
+                      });
+                      if self.settings.node.waglaylad_daemon_restart_enable {
+                        ui.horizontal(|ui| {
+                          ui.label(i18n("Maximum restart attempts:"));
+                          ui.add(egui::DragValue::new(&mut self.settings.node.waglaylad_daemon_restart_limit).range(1..=100))
+                            .on_hover_text_at_pointer(i18n("Number of consecutive crashes after which the node is no longer restarted."));
+                        });
+                      }
+                      ui.horizontal(|ui| {
+                        ui.label(i18n("Shutdown timeout (seconds):"));
+                        ui.add(egui::DragValue::new(&mut self.settings.node.waglaylad_daemon_shutdown_timeout).range(5..=600))
+                          .on_hover_text_at_pointer(i18n("Time the node is given to flush its database after a shutdown request before it is forcefully terminated. Takes effect on the next node start."));
                       });
                     });
                 },
@@ -244,6 +629,19 @@ impl Settings {
                         node_settings_error = Some(i18n("Data storage folder not found"));
                       }
                     }
+                    ui.add_space(4.);
+                    ui.horizontal(|ui| {
+                      ui.label(i18n("Warn when free disk space is below (GB):"));
+                      ui.add(egui::DragValue::new(&mut self.settings.node.disk_space_warning_gb).range(1..=10_000));
+                    });
+                    ui.horizontal(|ui| {
+                      ui.label(i18n("Stop the node when free disk space is below (GB):"));
+                      ui.add(egui::DragValue::new(&mut self.settings.node.disk_space_stop_gb).range(1..=10_000));
+                    });
+                    if self.settings.node.disk_space_stop_gb >= self.settings.node.disk_space_warning_gb {
+                      ui.colored_label(theme_color().error_color, i18n("The stop threshold must be lower than the warning threshold"));
+                      node_settings_error = Some(i18n("Invalid disk space thresholds"));
+                    }
                   });
               }
             });
@@ -254,6 +652,27 @@ impl Settings {
           self.settings.node.grpc_network_interface = self.grpc_network_interface.as_ref().try_into().unwrap(); //NetworkInterfaceConfig::try_from(&self.grpc_network_interface).unwrap();
         }
 
+        if !self.wrpc_borsh_network_interface.is_valid() {
+          node_settings_error = Some(i18n("Invalid wRPC Borsh network interface configuration"));
+        } else {
+          self.settings.node.wrpc_borsh_network_interface = self.wrpc_borsh_network_interface.as_ref().try_into().unwrap();
+        }
+
+        if !self.wrpc_json_network_interface.is_valid() {
+          node_settings_error = Some(i18n("Invalid wRPC JSON network interface configuration"));
+        } else {
+          self.settings.node.wrpc_json_network_interface = self.wrpc_json_network_interface.as_ref().try_into().unwrap();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.settings.node.node_kind.is_config_capable() && node_settings_error.is_none() {
+          use crate::dx_manager::services::waglayla::Config;
+          if let Some((first, second)) = Config::from(self.settings.node.clone()).listener_conflict() {
+            node_settings_error = Some(i18n("Node RPC listeners are configured on the same port"));
+            ui.colored_label(theme_color().error_color, format!("{} {} / {}", i18n("Port conflict between"), first, second));
+          }
+        }
+
         if self.settings.node.node_kind == WagLayladNodeKind::Remote {
           node_settings_error = Self::render_remote_settings(core, ui, &mut self.settings.node);
         }
@@ -268,7 +687,25 @@ impl Settings {
                   .default_open(true)
                   .show(ui, |ui| {
                     ui.vertical(|ui|{
-                      ui.checkbox(&mut self.settings.node.enable_wrpc_borsh, i18n("Public wRPC (Borsh)"));
+                      let in_proc = self.settings.node.node_kind == WagLayladNodeKind::IntegratedInProc;
+                      ui.add_enabled(!in_proc, egui::Checkbox::new(&mut self.settings.node.enable_wrpc_borsh, i18n("Enable wRPC (Borsh)")))
+                        .on_disabled_hover_text(i18n("The in-process node is reached directly and opens no wRPC Borsh port"));
+                      if self.settings.node.enable_wrpc_borsh && !in_proc {
+                        CollapsingHeader::new(i18n("wRPC Borsh Network Interface & Port"))
+                          .default_open(true)
+                          .show(ui, |ui| {
+                            self.wrpc_borsh_network_interface.ui(ui);
+                          });
+                      }
+
+                      ui.checkbox(&mut self.settings.node.enable_wrpc_json, i18n("Enable wRPC (JSON)"));
+                      if self.settings.node.enable_wrpc_json {
+                        CollapsingHeader::new(i18n("wRPC JSON Network Interface & Port"))
+                          .default_open(true)
+                          .show(ui, |ui| {
+                            self.wrpc_json_network_interface.ui(ui);
+                          });
+                      }
 
                       ui.checkbox(&mut self.settings.node.enable_grpc, i18n("Enable gRPC"));
                       if self.settings.node.enable_grpc {
@@ -288,6 +725,30 @@ impl Settings {
                     ui.checkbox(&mut self.settings.node.enable_upnp, i18n("Enable UPnP"));
                   });
                 });
+
+              CollapsingHeader::new(i18n("Resources"))
+                .default_open(false)
+                .show(ui, |ui| {
+                  Self::render_resource_settings(core, ui, &mut self.settings.node);
+                });
+
+              CollapsingHeader::new(i18n("Custom Arguments"))
+                .default_open(false)
+                .show(ui, |ui| {
+                  ui.vertical(|ui|{
+                    ui.checkbox(&mut self.settings.node.waglaylad_daemon_args_enable, i18n("Use custom daemon arguments"));
+                    if self.settings.node.waglaylad_daemon_args_enable {
+                      ui.add(TextEdit::multiline(&mut self.settings.node.waglaylad_daemon_args).desired_rows(2))
+                        .on_hover_text_at_pointer(i18n("Additional waglaylad command line arguments. Values containing spaces can be quoted."));
+
+                      use crate::dx_manager::services::waglayla::{Config, config::Args};
+                      if let Err(err) = Args::try_from(Config::from(self.settings.node.clone())) {
+                        ui.colored_label(theme_color().error_color, err.to_string());
+                        node_settings_error = Some(i18n("Invalid daemon arguments"));
+                      }
+                    }
+                  });
+                });
               });
             });
         }
@@ -305,8 +766,7 @@ impl Settings {
 
         if ui.button(i18n("Ok")).clicked() {
           self.settings.node = core.settings.node.clone();
-          self.grpc_network_interface =
-            NetworkInterfaceEditor::from(&self.settings.node.grpc_network_interface);
+          self.reset_network_interfaces();
         }
 
         ui.separator();
@@ -322,10 +782,15 @@ impl Settings {
                 cfg_if! {
                   if #[cfg(not(target_arch = "wasm32"))] {
                     let storage_root = core.settings.node.waglaylad_daemon_storage_folder_enable.then_some(core.settings.node.waglaylad_daemon_storage_folder.as_str());
+                    core.storage.track_network(core.settings.node.network);
                     core.storage.track_storage_root(storage_root);
                   }
                 }
 
+                #[cfg(not(target_arch = "wasm32"))]
+                self.manager.waglayla_service().update_restart_policy(&self.settings.node);
+                self.manager.failover_monitor().update_settings(&self.settings.node);
+
                 if restart {
                   self.manager.waglayla_service().update_services(&self.settings.node, None);
                   self.manager.bridge_service().update_services(&self.settings.node, None);
@@ -333,7 +798,8 @@ impl Settings {
               },
               Confirm::No => {
                 self.settings = core.settings.clone();
-                self.grpc_network_interface = NetworkInterfaceEditor::from(&self.settings.node.grpc_network_interface);
+                self.reset_network_interfaces();
+                self.bridge_settings = BridgeSettingsEditor::from(&self.settings.bridge);
               }
             }
           }
@@ -347,14 +813,14 @@ impl Settings {
     core: &mut Core,
     ui: &mut egui::Ui,
   ) {
-    if self.settings.node.node_kind != WagLayladNodeKind::IntegratedAsDaemon ||
-      !self.settings.node.enable_bridge 
-    {
+    if !self.settings.node.enable_bridge {
       return;
     }
+    let remote_node = !core.settings.node.node_kind.is_local();
 
-    let mut settings = &mut self.settings.bridge;
-    let mut bridge_settings_error: Option<&str> = None;
+    let settings = &mut self.settings.bridge;
+    let editor = &mut self.bridge_settings;
+    let mut bridge_settings_error: Option<String> = None;
 
     #[cfg(not(target_arch = "wasm32"))]
     CollapsingHeader::new(i18n("Stratum Bridge Config"))
@@ -363,7 +829,7 @@ impl Settings {
       // Edit fields
       ui.horizontal(|ui| {
         ui.label("Stratum Port:");
-        ui.text_edit_singleline(&mut settings.stratum_port)
+        ui.text_edit_singleline(&mut editor.stratum_port)
           .on_hover_text_at_pointer(i18n(
             "The port that will be listening for incoming stratum traffic, preceded by a colon ':'."
           ));
@@ -375,6 +841,22 @@ impl Settings {
             "The <address>:<port> combo of an available WagLayla node, i.e 127.0.0.1:13110."
           ));
       });
+      #[cfg(not(target_arch = "wasm32"))]
+      if remote_node {
+        ui.horizontal(|ui| {
+          let address = settings.waglayla_address.trim().to_string();
+          let test = editor.connection_test(&address);
+          if ui.add_enabled(!matches!(test, Some(None)), egui::Button::new(i18n("Test Connection"))).clicked() {
+            editor.test_connection(&address);
+          }
+          match test {
+            Some(None) => { ui.label(i18n("Connecting...")); },
+            Some(Some(Ok(()))) => { ui.label(RichText::new(i18n("The node answered over gRPC")).color(theme_color().strong_color)); },
+            Some(Some(Err(err))) => { ui.label(RichText::new(err).color(theme_color().error_color)); },
+            None => {},
+          }
+        });
+      }
       ui.horizontal(|ui| {
         ui.label("Min Share Diff:");
         ui.add(egui::DragValue::new(&mut settings.min_share_diff).speed(1))
@@ -409,7 +891,7 @@ impl Settings {
       });
       ui.horizontal(|ui| {
         ui.label("Block Wait Time:");
-        ui.text_edit_singleline(&mut settings.block_wait_time)
+        ui.text_edit_singleline(&mut editor.block_wait_time)
           .on_hover_text_at_pointer(i18n(
             "Amount of time to wait after a new block message from waglaylad before manually requesting a fresh template."
           ));
@@ -437,13 +919,33 @@ impl Settings {
       });
       ui.horizontal(|ui| {
         ui.label("Prometheus Port:");
-        ui.text_edit_singleline(&mut settings.prom_port)
+        ui.text_edit_singleline(&mut editor.prom_port)
           .on_hover_text_at_pointer(i18n(
             "Prometheus will serve stats on the port provided."
           ));
       });
 
-      if let Some(error) = bridge_settings_error {
+      bridge_settings_error = editor.apply(settings).err();
+
+      #[cfg(not(target_arch = "wasm32"))]
+      if bridge_settings_error.is_none() {
+        use crate::dx_manager::services::waglayla::Config;
+        // a remote node leaves all local ports to the bridge
+        let listeners = if remote_node { vec![] } else { Config::from(core.settings.node.clone()).listeners() };
+        bridge_settings_error = settings.validate(&listeners).err();
+      }
+
+      if bridge_settings_error.is_none() && remote_node && settings.targets_loopback() {
+        bridge_settings_error = Some(i18n("No local node is running. Set the WagLayla address to the gRPC listener of a remote node.").to_string());
+      }
+
+      #[cfg(not(target_arch = "wasm32"))]
+      if bridge_settings_error.is_none() && *settings != core.settings.bridge {
+        let running = self.manager.bridge_service().is_enabled.load(Ordering::Relaxed);
+        bridge_settings_error = editor.check_availability(settings, running.then_some(&core.settings.bridge));
+      }
+
+      if let Some(error) = bridge_settings_error.as_ref() {
         ui.add_space(4.);
         ui.label(
           RichText::new(error)
@@ -454,8 +956,7 @@ impl Settings {
 
         if ui.button(i18n("Ok")).clicked() {
           *settings = core.settings.bridge.clone();
-          self.grpc_network_interface =
-            NetworkInterfaceEditor::from(&self.settings.node.grpc_network_interface);
+          *editor = BridgeSettingsEditor::from(&core.settings.bridge);
         }
 
         ui.separator();
@@ -467,19 +968,13 @@ impl Settings {
               Confirm::Yes => {
                 core.settings.bridge = settings.clone();
                 core.settings.store_sync().unwrap();
-
-                cfg_if! {
-                  if #[cfg(not(target_arch = "wasm32"))] {
-                    let storage_root = core.settings.node.waglaylad_daemon_storage_folder_enable.then_some(core.settings.node.waglaylad_daemon_storage_folder.as_str());
-                    core.storage.track_storage_root(storage_root);
-                  }
-                }
-
-                self.manager.stop_services();
-                self.manager.start_services();
+                self.manager.bridge_service().update_settings(&core.settings);
+                self.manager.bridge_metrics().update_settings(&core.settings.bridge);
+                self.manager.bridge_service().reconfigure();
               },
               Confirm::No => {
                 *settings = core.settings.bridge.clone();
+                *editor = BridgeSettingsEditor::from(&core.settings.bridge);
               }
             }
           }
@@ -489,6 +984,138 @@ impl Settings {
     });
   }
 
+  #[cfg(not(target_arch = "wasm32"))]
+  fn render_log_settings(
+    &mut self,
+    core: &mut Core,
+    ui: &mut egui::Ui,
+  ) {
+    CollapsingHeader::new(i18n("Log Files"))
+      .default_open(false)
+      .show(ui, |ui| {
+        let settings = &mut self.settings.logs;
+
+        ui.horizontal(|ui| {
+          ui.label(i18n("Maximum size (MB):"));
+          ui.add(egui::DragValue::new(&mut settings.max_size_mb).range(1..=10_000))
+            .on_hover_text_at_pointer(i18n("The active log file is rotated once it grows past this size."));
+        });
+        ui.horizontal(|ui| {
+          ui.label(i18n("Maximum age (hours):"));
+          ui.add(egui::DragValue::new(&mut settings.max_age_hours).range(0..=24 * 365))
+            .on_hover_text_at_pointer(i18n("The active log file is rotated once it is older than this. 0 disables age-based rotation."));
+        });
+        ui.horizontal(|ui| {
+          ui.label(i18n("Rotated files to keep:"));
+          ui.add(egui::DragValue::new(&mut settings.retention).range(0..=100))
+            .on_hover_text_at_pointer(i18n("Number of rotated log files kept next to the active log. Older files are deleted."));
+        });
+        ui.horizontal(|ui| {
+          ui.add(toggle(&mut settings.compress))
+            .on_hover_text_at_pointer(i18n("Compress rotated log files with gzip."));
+          ui.label(i18n("Compress rotated logs"));
+        });
+
+        if *settings != core.settings.logs {
+          if let Some(response) = ui.confirm_widget_labels("Apply", "Cancel") {
+            match response {
+              Confirm::Yes => {
+                core.settings.logs = settings.clone();
+                core.settings.store_sync().unwrap();
+
+                self.manager.waglayla_service().update_log_settings(&core.settings.logs);
+                self.manager.bridge_service().update_settings(&core.settings);
+              },
+              Confirm::No => {
+                *settings = core.settings.logs.clone();
+              }
+            }
+          }
+          ui.separator();
+        }
+      });
+  }
+
+  fn render_health_settings(
+    &mut self,
+    core: &mut Core,
+    ui: &mut egui::Ui,
+  ) {
+    CollapsingHeader::new(i18n("Node Health Monitor"))
+      .default_open(false)
+      .show(ui, |ui| {
+        let settings = &mut self.settings.health;
+        let mut health_settings_error = None;
+
+        ui.horizontal(|ui| {
+          ui.add(toggle(&mut settings.enable))
+            .on_hover_text_at_pointer(i18n("Watch the node for stalled sync progress and lost peers."));
+          ui.label(i18n("Enable Health Monitor"));
+        });
+
+        if settings.enable {
+          ui.horizontal(|ui| {
+            ui.label(i18n("Stall timeout (minutes):"));
+            ui.add(egui::DragValue::new(&mut settings.stall_timeout_minutes).range(1..=240))
+              .on_hover_text_at_pointer(i18n("The node is considered stalled when its DAA score, header and block counts do not change for this long."));
+          });
+          ui.horizontal(|ui| {
+            ui.label(i18n("No peers timeout (minutes):"));
+            ui.add(egui::DragValue::new(&mut settings.no_peers_timeout_minutes).range(1..=240));
+          });
+
+          ui.horizontal_wrapped(|ui| {
+            ui.label(i18n("Remediation:"));
+            HealthRemediation::iter().for_each(|remediation| {
+              ui.radio_value(&mut settings.remediation, *remediation, remediation.to_string());
+            });
+          });
+
+          if settings.remediation != HealthRemediation::Notify {
+            ui.horizontal(|ui| {
+              ui.label(i18n("Maximum attempts:"));
+              ui.add(egui::DragValue::new(&mut settings.max_remediations).range(1..=100))
+                .on_hover_text_at_pointer(i18n("Remediation attempts before the monitor only reports the problem. The count resets once the node is healthy."));
+            });
+          }
+
+          if settings.remediation == HealthRemediation::FallbackRemote {
+            ui.horizontal(|ui| {
+              ui.label(i18n("Fallback wRPC URL:"));
+              ui.add(TextEdit::singleline(&mut settings.fallback_url));
+            });
+
+            let node = &core.settings.node;
+            if settings.fallback_url.trim().is_empty() {
+              health_settings_error = Some(i18n("Please enter the wRPC URL of the fallback node"));
+            } else if let Err(err) = WaglaylaRpcClient::parse_url(settings.fallback_url.trim().to_string(), node.wrpc_encoding, node.network.into()) {
+              ui.colored_label(theme_color().warning_color, err.to_string());
+              health_settings_error = Some(i18n("Invalid wRPC URL"));
+            }
+          }
+        }
+
+        if let Some(error) = health_settings_error {
+          ui.colored_label(theme_color().error_color, error);
+        } else if *settings != core.settings.health {
+          if let Some(response) = ui.confirm_widget_labels("Apply", "Cancel") {
+            match response {
+              Confirm::Yes => {
+                core.settings.health = settings.clone();
+                core.settings.store_sync().unwrap();
+
+                self.manager.health_monitor().update_settings(&core.settings.health);
+              },
+              Confirm::No => {
+                *settings = core.settings.health.clone();
+              }
+            }
+          }
+          ui.separator();
+        }
+      });
+  }
+
   fn render_ui_settings(
     &mut self,
     core: &mut Core,
@@ -586,9 +1213,24 @@ impl Settings {
 
     self.render_ui_settings(core,ui);
     self.render_node_settings(core,ui);
+    self.render_health_settings(core,ui);
     self.render_bridge_settings(core,ui);
+    #[cfg(not(target_arch = "wasm32"))]
+    self.render_log_settings(core,ui);
         
     #[cfg(not(target_arch = "wasm32"))]
     core.storage.clone().render_settings(core, ui);
   }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+fn total_memory() -> Option<u64> {
+  use sysinfo::{System, SystemExt};
+  static TOTAL_MEMORY: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
+  let total = *TOTAL_MEMORY.get_or_init(|| {
+    let mut system = System::new();
+    system.refresh_memory();
+    system.total_memory()
+  });
+  (total > 0).then_some(total)
+}
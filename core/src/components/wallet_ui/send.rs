@@ -98,14 +98,15 @@ impl WalletSend {
   }
 
   
-  fn render_details_state(&mut self, ui: &mut egui::Ui) {
+  fn render_details_state(&mut self, ui: &mut egui::Ui, network: Network) {
+    let prefix = waglayla_addresses::Prefix::from(network);
     ui.heading(i18n("Recipient Address:"));
     let address_response = ui.add_sized(
       [ui.available_width(), 35.0],
       egui::TextEdit::singleline(
         &mut self.address,
       )
-        .hint_text(i18n("waglayla: prefix required"))
+        .hint_text(format!("{}: {}", prefix, i18n("prefix required")))
         .font(FontId::proportional(EDIT_SIZE))
         .vertical_align(Align::Center)
         .frame(true)
@@ -138,8 +139,7 @@ impl WalletSend {
     ui.add_space(16.0);
 
     let enabled = !self.address.trim().is_empty() 
-      && self.address.contains("waglayla:")
-      && self.address.chars().all(|c| c.is_alphanumeric() || c == ':')
+      && Address::try_from(self.address.trim()).map(|address| address.prefix == prefix).unwrap_or(false)
       && self.amount_sompi.is_some()
       && self.error.is_none();
 
@@ -268,7 +268,7 @@ impl WalletSend {
     });
   }
 
-  fn render_success_state(&mut self, ui: &mut egui::Ui) {
+  fn render_success_state(&mut self, ui: &mut egui::Ui, network: Network) {
     let window_rect = ui.min_rect();
     let coin_diameter = 200.0;
     let image_pos = pos2(
@@ -295,7 +295,7 @@ impl WalletSend {
             &tx_id_str.as_str()[..32.min(tx_id_str.len())]
           );
           
-          let link = format!("https://explorer.waglayla.com/txs/{}", tx_id);
+          let link = format!("{}/txs/{}", network.explorer(), tx_id);
 
           let response = ui.add(
             egui::Label::new(
@@ -391,9 +391,9 @@ impl WalletSend {
               }
 
               match self.state {
-                SendState::Details => self.render_details_state(ui),
+                SendState::Details => self.render_details_state(ui, core.settings.node.network),
                 SendState::Confirm => self.render_confirm_state(ui, core),
-                SendState::Success => self.render_success_state(ui),
+                SendState::Success => self.render_success_state(ui, core.settings.node.network),
               }
 
               if let Some(error) = &self.error {
@@ -415,7 +415,7 @@ impl WalletSend {
     *self.is_pending.lock().unwrap() = true;
 
     let payment_output = PaymentOutput {
-      address: Address::try_from(address).unwrap(),
+      address: Address::try_from(address.trim()).unwrap(),
       amount,
     };
 
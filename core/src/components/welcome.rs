@@ -109,7 +109,7 @@ impl Welcome {
 
               if self.settings.node.node_kind == WagLayladNodeKind::Remote {
                 error = crate::components::settings::Settings::render_remote_settings(core,ui,&mut self.settings.node);
-              } else if self.settings.node.node_kind == WagLayladNodeKind::IntegratedAsDaemon {
+              } else if self.settings.node.node_kind.is_local() {
                 error = crate::components::settings::Settings::render_node_storage_settings(core,ui,&mut self.settings.node);
               }
           });
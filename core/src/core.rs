@@ -2,6 +2,7 @@ use crate::gui::frame::*;
 use crate::imports::*;
 
 use crate::components::*;
+use crate::dx_manager::services::{DiscoveryStatus, NodeIncompatibility};
 
 // use crate::market::*; TODO: make our own market monitoring solution
 // use crate::mobile::MobileMenu; TODO: make own version of this
@@ -13,16 +14,17 @@ use std::future::IntoFuture;
 #[allow(unused_imports)]
 use workflow_i18n::*;
 pub const TRANSACTION_PAGE_SIZE: u64 = 20;
-pub const MAINNET_EXPLORER: &str = "https://explorer.waglayla.org";
+pub const MAINNET_EXPLORER: &str = "https://explorer.waglayla.com";
 pub const TESTNET10_EXPLORER: &str = "https://explorer-tn10.waglayla.org";
 pub const TESTNET11_EXPLORER: &str = "https://explorer-tn11.waglayla.org";
+pub const DISK_SPACE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+pub const STORAGE_UPDATE_TICKS: u64 = 6;
 
 use eframe::egui::{self, Context};
 
 #[derive(Clone)]
 pub enum Exception {
-  #[allow(dead_code)]
-  UtxoIndexNotEnabled { url: Option<String> },
+  IncompatibleNode { url: Option<String>, reason: NodeIncompatibility },
 }
 
 #[derive(Clone, Debug)]
@@ -115,6 +117,8 @@ impl Core {
 
     let storage = Storage::default();
     #[cfg(not(target_arch = "wasm32"))]
+    storage.track_network(settings.node.network);
+    #[cfg(not(target_arch = "wasm32"))]
     if settings.node.waglaylad_daemon_storage_folder_enable {
       storage.track_storage_root(Some(settings.node.waglaylad_daemon_storage_folder.as_str()));
     }
@@ -164,10 +168,19 @@ impl Core {
         this.register_visibility_handler();
       } else {
         let storage = this.storage.clone();
+        let sender = this.application_events_channel.sender.clone();
         tokio::spawn(async move {
+          // free space is polled more often than the (expensive) folder size
+          let mut tick = 0u64;
           loop {
-            storage.update();
-            tokio::time::sleep(Duration::from_secs(60)).await;
+            if tick % STORAGE_UPDATE_TICKS == 0 {
+              storage.update();
+            }
+            if let Some(disk) = storage.update_disk_space() {
+              sender.try_send(Events::DiskSpace(disk)).ok();
+            }
+            tick += 1;
+            tokio::time::sleep(DISK_SPACE_CHECK_INTERVAL).await;
           }
           Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
         });
@@ -322,6 +335,8 @@ impl Core {
         });
       });
     });
+
+    self.render_exception(ctx);
   }
 
   pub fn set_active_component<T: ComponentT + 'static>(&mut self) {
@@ -443,6 +458,74 @@ impl Core {
           self.node_state.node_peers = Some(count);
         }
 
+        Events::BridgeMetrics(report) => {
+          // a failed scrape keeps the last known figures on screen
+          match (report.error.clone(), self.node_state.bridge_metrics.as_mut()) {
+            (Some(error), Some(current)) => current.error = Some(error),
+            _ => self.node_state.bridge_metrics = Some(report),
+          }
+        }
+
+        Events::BridgeReconfigure(status) => {
+          self.node_state.bridge_reconfigure = Some(status);
+        }
+
+        Events::BridgeNode(status) => {
+          self.node_state.bridge_node = status;
+        }
+
+        Events::RemoteEndpoints(report) => {
+          self.node_state.remote_endpoints = Some(report);
+        }
+
+        Events::RemoteFailover { url, reason } => {
+          // the switch is temporary, the stored settings are applied again on the next restart
+          let mut node_settings = self.settings.node.clone();
+          node_settings.wrpc_url = url.clone();
+          self.manager.waglayla_service().update_services(&node_settings, None);
+          self.add_notification(
+            &format!("{} {} ({})", i18n("Switching to remote node"), url, reason),
+            ToastKind::Info,
+            10,
+          );
+        }
+
+        Events::NodeDiscovery(status) => {
+          match &status {
+            DiscoveryStatus::Found { .. } => {
+              self.add_notification(&status.to_string(), ToastKind::Info, 5);
+            }
+            DiscoveryStatus::Failed => {
+              self.add_notification(&status.to_string(), ToastKind::Error, 10);
+              self.node_state.error = Some(status.to_string());
+            }
+            _ => {}
+          }
+          self.node_state.discovery = (status != DiscoveryStatus::Cancelled).then_some(status);
+        }
+
+        Events::RemoteConnectionError { error } => {
+          self.add_notification(&format!("{}: {}", i18n("Remote node connection error"), error), ToastKind::Error, 15);
+          self.node_state.error = Some(error);
+        }
+
+        Events::RemoteNodeRejected { url, reason } => {
+          self.reject_node(url, reason);
+        }
+
+        Events::NodeHealth { state, remediation } => {
+          if state.is_problem() && state != self.node_state.health {
+            if let Some(description) = state.describe() {
+              self.add_notification(&description, ToastKind::Error, 10);
+            }
+          }
+          self.node_state.health = state;
+
+          if let Some(remediation) = remediation {
+            self.remediate_node_health(remediation);
+          }
+        }
+
         Events::BlockRewardUpdate(reward) => {
           self.node_state.block_reward = Some(reward);
         }
@@ -464,6 +547,86 @@ impl Core {
           self.node_state.node_mempool_size = Some(count);
         }
 
+        Events::DaemonStarted { crashes } => {
+          self.node_state.sync_progress = Default::default();
+          self.node_state.daemon_crashes = crashes;
+          self.node_state.daemon_restart_pending = false;
+          self.node_state.daemon_halted = false;
+          self.node_state.disk_space_halted = false;
+          if crashes > 0 {
+            self.add_notification(i18n("WagLayla node restarted"), ToastKind::Info, 5);
+          }
+        }
+
+        Events::DaemonStopping => {
+          self.node_state.daemon_stopping = true;
+        }
+
+        Events::DaemonStopped => {
+          self.node_state.daemon_stopping = false;
+        }
+
+        Events::DaemonVersion { version, compatible } => {
+          if !compatible {
+            self.add_notification(
+              &format!("{} {} ({} {})", i18n("Incompatible waglaylad version"), version, i18n("expected"), waglayla_wallet_core::version()),
+              ToastKind::Error,
+              10,
+            );
+          }
+          self.node_state.daemon_version = Some(version);
+        }
+
+        Events::DaemonStartError { error } => {
+          self.add_notification(&format!("{}: {}", i18n("Unable to start waglaylad"), error), ToastKind::Error, 10);
+          self.node_state.error = Some(error);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        Events::DiskSpace(disk) => {
+          self.check_disk_space(disk);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        Events::StorageMigration(migration) => {
+          match &migration.stage {
+            crate::storage::MigrationStage::Done => {
+              self.settings.node.waglaylad_daemon_storage_folder_enable = true;
+              self.settings.node.waglaylad_daemon_storage_folder = migration.target_root.display().to_string();
+              self.settings.store_sync()?;
+              self.get_mut::<components::settings::Settings>().load(self.settings.clone());
+              self.storage.track_storage_root(Some(self.settings.node.waglaylad_daemon_storage_folder.as_str()));
+              self.add_notification(i18n("Node data folder moved"), ToastKind::Success, 5);
+              if let Some(leftover) = migration.leftover.as_ref() {
+                self.add_notification(leftover, ToastKind::Info, 10);
+              }
+              self.manager.waglayla_service().update_services(&self.settings.node, None);
+            }
+            crate::storage::MigrationStage::Failed(err) => {
+              self.add_notification(&format!("{}: {}", i18n("Unable to move node data folder"), err), ToastKind::Error, 10);
+              self.manager.waglayla_service().update_services(&self.settings.node, None);
+            }
+            _ => {}
+          }
+          self.storage.set_migration(Some(migration));
+        }
+
+        Events::DaemonCrash { crashes, status, restart_in } => {
+          self.node_state.daemon_crashes = crashes;
+          self.node_state.daemon_exit_status = Some(status);
+          self.node_state.daemon_restart_pending = restart_in.is_some();
+          self.node_state.daemon_halted = restart_in.is_none();
+          if restart_in.is_some() {
+            self.add_notification(i18n("WagLayla node crashed, restarting..."), ToastKind::Error, 5);
+          } else {
+            self.add_notification(i18n("WagLayla node crashed and will not be restarted"), ToastKind::Error, 10);
+          }
+        }
+
+        Events::NodeLog(event) => {
+          self.node_state.sync_progress.update(event);
+        }
+
         Events::WalletUpdate => {
           self.update_wallet();
         }
@@ -562,7 +725,10 @@ impl Core {
               // self.network_pressure.clear();
             }
             CoreWallet::UtxoIndexNotEnabled { url } => {
-              // self.exception = Some(Exception::UtxoIndexNotEnabled { url });
+              if self.settings.node.node_kind == WagLayladNodeKind::Remote {
+                self.manager.waglayla_service().stop_node();
+              }
+              self.reject_node(url, NodeIncompatibility::UtxoIndexNotEnabled);
             }
             CoreWallet::SyncState { sync_state } => {
               println!("Sync State: {:?}", sync_state);
@@ -574,6 +740,21 @@ impl Core {
               url,
               network_id,
             } => {
+              // explicit urls are verified before the wallet is bound; nodes picked by a resolver are only known once connected
+              if self.settings.node.node_kind == WagLayladNodeKind::Remote && self.exception.is_none() {
+                let expected = NetworkId::from(self.settings.node.network);
+                if let Some(reason) = crate::dx_manager::services::waglayla::verify::check_server(network_id, None, &server_version, expected) {
+                  self.manager.waglayla_service().stop_node();
+                  self.reject_node(url.clone(), reason);
+                } else if !is_synced && self.node_state.url != url {
+                  self.add_notification(
+                    i18n("The remote node is still syncing, balances may be incomplete until it catches up"),
+                    ToastKind::Info,
+                    10,
+                  );
+                }
+              }
+
               self.node_state.is_synced = Some(is_synced);
               self.node_state.server_version = Some(server_version);
               self.node_state.url = url;
@@ -593,7 +774,7 @@ impl Core {
               let network_id = self
                 .node_state
                 .network_id
-                .unwrap_or(Network::Mainnet.into());
+                .unwrap_or(self.settings.node.network.into());
               let account_descriptors =
                 account_descriptors.ok_or(Error::WalletOpenAccountDescriptors)?;
               self.load_accounts(network_id, account_descriptors)?;
@@ -608,7 +789,7 @@ impl Core {
               let network_id = self
                 .node_state
                 .network_id
-                .unwrap_or(Network::Mainnet.into());
+                .unwrap_or(self.settings.node.network.into());
               let account_descriptors =
                 account_descriptors.ok_or(Error::WalletOpenAccountDescriptors)?;
               self.load_accounts(network_id, account_descriptors)?;
@@ -851,6 +1032,122 @@ impl Core {
     Ok(())
   }
 
+  /// Warns when free space next to the data directory runs low and stops
+  /// the node before the volume fills up and corrupts the database.
+  #[cfg(not(target_arch = "wasm32"))]
+  fn check_disk_space(&mut self, disk: crate::storage::DiskSpace) {
+    const GB: u64 = 1_000_000_000;
+    let warning = self.settings.node.disk_space_warning_gb * GB;
+    let stop = self.settings.node.disk_space_stop_gb * GB;
+
+    if disk.available < stop && self.settings.node.node_kind.is_local() {
+      if !self.node_state.disk_space_halted && !self.storage.is_migrating() {
+        self.node_state.disk_space_halted = true;
+        self.manager.waglayla_service().stop_node();
+        self.add_notification(i18n("Disk space is critically low, the node has been stopped"), ToastKind::Error, 30);
+      }
+    } else if disk.available < warning {
+      if !self.node_state.disk_space_low {
+        self.add_notification(
+          &format!("{}: {}", i18n("Disk space is running low"), crate::storage::format_size(disk.available)),
+          ToastKind::Error,
+          15,
+        );
+      }
+    }
+    self.node_state.disk_space_low = disk.available < warning;
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn resume_after_disk_space(&mut self) {
+    self.node_state.disk_space_halted = false;
+    self.manager.waglayla_service().update_services(&self.settings.node, None);
+  }
+
+  /// Records a remote node that was refused so that the reason is explained to the user
+  fn reject_node(&mut self, url: Option<String>, reason: NodeIncompatibility) {
+    self.node_state.error = Some(reason.to_string());
+    self.exception = Some(Exception::IncompatibleNode { url, reason });
+  }
+
+  fn render_exception(&mut self, ctx: &Context) {
+    let Some(Exception::IncompatibleNode { url, reason }) = self.exception.clone() else {
+      return;
+    };
+
+    let mut dismiss = false;
+    let mut open_settings = false;
+    egui::Window::new(i18n("Incompatible Node"))
+      .collapsible(false)
+      .resizable(false)
+      .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+      .max_width(420.0)
+      .frame(create_custom_popup(ctx))
+      .show(ctx, |ui| {
+        egui::Frame::none()
+          .inner_margin(12.0)
+          .show(ui, |ui| {
+            ui.vertical_centered(|ui| {
+              ui.label(
+                egui::RichText::new(egui_phosphor::bold::WARNING)
+                  .size(48.0)
+                  .color(theme_color().error_color),
+              );
+              ui.add_space(8.0);
+              ui.label(i18n("The wallet refused to connect to this node."));
+              if let Some(url) = url.as_ref() {
+                ui.label(egui::RichText::new(url).monospace());
+              }
+              ui.add_space(8.0);
+              ui.label(egui::RichText::new(reason.to_string()).strong());
+              ui.add_space(4.0);
+              ui.label(reason.remedy());
+              ui.add_space(12.0);
+              ui.horizontal(|ui| {
+                if ui.button(i18n("Open Settings")).clicked() {
+                  open_settings = true;
+                }
+                if ui.button(i18n("Dismiss")).clicked() {
+                  dismiss = true;
+                }
+              });
+            });
+          });
+      });
+
+    if open_settings {
+      self.set_active_component::<components::Settings>();
+    }
+    if open_settings || dismiss {
+      self.exception = None;
+    }
+  }
+
+  fn remediate_node_health(&mut self, remediation: HealthRemediation) {
+    let service = self.manager.waglayla_service().clone();
+    match remediation {
+      HealthRemediation::Notify => {}
+      HealthRemediation::ReconnectRpc => service.reconnect_rpc(),
+      HealthRemediation::RestartNode if self.settings.node.node_kind.is_local() => {
+        service.update_services(&self.settings.node, None);
+      }
+      HealthRemediation::RestartNode => service.reconnect_rpc(),
+      HealthRemediation::FallbackRemote => {
+        // the fallback is temporary, the stored settings are applied again on the next restart
+        match self.settings.health.fallback_node_settings(&self.settings.node) {
+          Some(fallback) => service.update_services(&fallback, None),
+          None => service.reconnect_rpc(),
+        }
+      }
+    }
+
+    self.add_notification(
+      &format!("{}: {}", i18n("Node stalled, attempting recovery"), remediation),
+      ToastKind::Info,
+      10,
+    );
+  }
+
   pub fn add_notification(&mut self, message: &str, kind: ToastKind, duration: u64) {
     let mut toasts = self.toasts.lock().unwrap();
     match kind {
@@ -926,7 +1223,7 @@ impl Core {
     range: std::ops::Range<u64>,
   ) -> Result<()> {
     let account_id = account.id();
-    let network_id = Network::Mainnet.into();
+    let network_id = self.node_state.network_id.unwrap_or(self.settings.node.network.into());
     let manager = self.manager.clone();
     let account = account.clone();
     tokio::spawn(async move {
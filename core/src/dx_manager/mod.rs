@@ -27,6 +27,10 @@ pub struct Inner {
   peer_monitor: Arc<PeerMonitorService>,
   bridge_service: Arc<BridgeService>,
   stat_monitor: Arc<StatMonitorService>,
+  health_monitor: Arc<HealthMonitorService>,
+  failover_monitor: Arc<FailoverMonitorService>,
+  node_discovery: Arc<NodeDiscoveryService>,
+  bridge_metrics: Arc<BridgeMetricsService>,
   daemon_channel : Channel<DaemonMessage>,
   bridge_channel : Channel<DaemonMessage>,
 }
@@ -68,11 +72,35 @@ impl DX_Manager {
       settings,
     ));
 
+    let health_monitor = Arc::new(HealthMonitorService::new(
+      application_events.clone(),
+      settings,
+    ));
+
+    let failover_monitor = Arc::new(FailoverMonitorService::new(
+      application_events.clone(),
+      settings,
+    ));
+
+    let node_discovery = Arc::new(NodeDiscoveryService::new(
+      application_events.clone(),
+      settings,
+    ));
+
+    let bridge_metrics = Arc::new(BridgeMetricsService::new(
+      application_events.clone(),
+      settings,
+    ));
+
     let services: Mutex<Vec<Arc<dyn Service>>> = Mutex::new(vec![
       waglayla.clone(),
       bridge_service.clone(),
       peer_monitor.clone(),
       stat_monitor.clone(),
+      health_monitor.clone(),
+      failover_monitor.clone(),
+      node_discovery.clone(),
+      bridge_metrics.clone(),
     ]);
 
     let manager = Self {
@@ -86,6 +114,10 @@ impl DX_Manager {
         bridge_service,
         peer_monitor,
         stat_monitor,
+        health_monitor,
+        failover_monitor,
+        node_discovery,
+        bridge_metrics,
         daemon_channel: daemon_channel.clone(),
         bridge_channel: bridge_channel.clone(),
         // system: Some(system),
@@ -181,6 +213,22 @@ impl DX_Manager {
     &self.inner.stat_monitor
   }
 
+  pub fn health_monitor(&self) -> &Arc<HealthMonitorService> {
+    &self.inner.health_monitor
+  }
+
+  pub fn failover_monitor(&self) -> &Arc<FailoverMonitorService> {
+    &self.inner.failover_monitor
+  }
+
+  pub fn node_discovery(&self) -> &Arc<NodeDiscoveryService> {
+    &self.inner.node_discovery
+  }
+
+  pub fn bridge_metrics(&self) -> &Arc<BridgeMetricsService> {
+    &self.inner.bridge_metrics
+  }
+
   pub fn wallet(&self) -> Arc<dyn WalletApi> {
     self.inner.waglayla.wallet()
   }
@@ -8,7 +8,9 @@ use std::process::Stdio;
 use tokio::sync::Mutex;
 use tokio::process::{Command, Child};
 use waglayla_wallet_core::storage::local::storage::Storage;
-use tokio::io::{AsyncRead, AsyncBufReadExt, BufReader};
+use tokio::io::{AsyncRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use crate::platform::log_rotation::ExternalLogFile;
+use sha2::{Digest, Sha256};
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
@@ -16,18 +18,164 @@ use std::os::windows::process::CommandExt;
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+// written by the bridge into its working directory when `log_to_file` is set
+const BRIDGE_LOG_FILE: &str = "bridge.log";
+// sidecar recording the SHA-256 digest of the extracted bridge binary
+const BRIDGE_DIGEST_EXTENSION: &str = "sha256";
+// time the bridge is given to exit after SIGTERM before it is killed
+const BRIDGE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+// a reconfigured bridge that stays up this long is considered to have accepted its config
+const BRIDGE_STARTUP_GRACE: Duration = Duration::from_secs(5);
+// time a remote node is given to accept a connection and answer the HTTP/2 handshake
+const NODE_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+// HTTP/2 client connection preface followed by an empty SETTINGS frame
+const HTTP2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n\0\0\0\x04\0\0\0\0\0";
+const HTTP2_SETTINGS_FRAME: u8 = 0x04;
+
+fn sha256_hex(data: &[u8]) -> String {
+  Sha256::digest(data).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn file_digest(path: &Path) -> std::io::Result<String> {
+  let mut hasher = Sha256::new();
+  let mut file = File::open(path)?;
+  std::io::copy(&mut file, &mut hasher)?;
+  Ok(hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// Writes `data` to a temporary file next to `path` and renames it over `path`,
+/// so that an interrupted write never leaves a truncated file behind
+fn write_atomic(path: &Path, data: &[u8], executable: bool) -> std::io::Result<()> {
+  let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or("bridge");
+  let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+  let result = (|| {
+    let mut file = File::create(&temp_path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    drop(file);
+
+    #[cfg(unix)]
+    if executable {
+      use std::os::unix::fs::PermissionsExt;
+      std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    #[cfg(not(unix))]
+    let _ = executable;
+
+    std::fs::rename(&temp_path, path)
+  })();
+
+  if result.is_err() {
+    let _ = std::fs::remove_file(&temp_path);
+  }
+  result
+}
+
+/// Makes sure `target_path` holds the bridge embedded in this build. The binary is
+/// extracted again when the recorded digest differs (e.g. after an upgrade) or when
+/// the file on disk no longer matches it.
+fn ensure_bridge_binary(target_path: &Path) -> Result<()> {
+  let expected = sha256_hex(BINARY);
+  let digest_path = target_path.with_extension(BRIDGE_DIGEST_EXTENSION);
+  let recorded = std::fs::read_to_string(&digest_path).map(|digest| digest.trim().to_string()).ok();
+
+  if recorded.as_deref() == Some(expected.as_str()) {
+    match file_digest(target_path) {
+      Ok(digest) if digest == expected => return Ok(()),
+      Ok(_) => eprintln!("Bridge binary at {} is corrupted, extracting it again", target_path.display()),
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+      Err(err) => eprintln!("Unable to verify bridge binary at {}: {}", target_path.display(), err),
+    }
+  } else if target_path.exists() {
+    println!("Updating bridge binary at {}", target_path.display());
+  }
+
+  write_atomic(target_path, BINARY, true)?;
+  let digest = file_digest(target_path)?;
+  if digest != expected {
+    return Err(Error::custom(format!(
+      "Extracted bridge binary failed verification (expected {expected}, found {digest})"
+    )));
+  }
+  write_atomic(&digest_path, expected.as_bytes(), false)?;
+  Ok(())
+}
+
 pub enum BridgeEvents {
   Enable,
   Disable,
+  Reconfigure,
   Exit,
 }
 
+/// Progress of a bridge restart requested by [`BridgeService::reconfigure()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReconfigureStatus {
+  Restarting,
+  Applied,
+  Failed(String),
+}
+
+impl std::fmt::Display for ReconfigureStatus {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ReconfigureStatus::Restarting => write!(f, "{}", i18n("Restarting the bridge with the new configuration...")),
+      ReconfigureStatus::Applied => write!(f, "{}", i18n("The bridge is running with the new configuration")),
+      ReconfigureStatus::Failed(reason) => write!(f, "{}: {}", i18n("The bridge failed to start with the new configuration"), reason),
+    }
+  }
+}
+
+/// Connection to the remote node the bridge mines against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BridgeNodeStatus {
+  Connected(String),
+  Unreachable { address: String, reason: String },
+}
+
+impl std::fmt::Display for BridgeNodeStatus {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      BridgeNodeStatus::Connected(address) => write!(f, "{} {}", i18n("Mining against the remote node at"), address),
+      BridgeNodeStatus::Unreachable { address, reason } => write!(f, "{} {}: {}", i18n("Unable to reach the remote node at"), address, reason),
+    }
+  }
+}
+
+/// Checks that `address` accepts connections and answers the HTTP/2 handshake
+/// gRPC runs on, so that a wRPC or HTTP port is refused before the bridge starts.
+pub async fn check_node_connection(address: &str) -> std::result::Result<(), String> {
+  let check = async {
+    let mut stream = tokio::net::TcpStream::connect(address.trim()).await.map_err(|err| err.to_string())?;
+    stream.write_all(HTTP2_PREFACE).await.map_err(|err| err.to_string())?;
+    // the server answers with its own SETTINGS frame; only the 9 byte frame header is needed
+    let mut header = [0u8; 9];
+    stream.read_exact(&mut header).await
+      .map_err(|_| i18n("The node closed the connection, the address does not appear to be a gRPC listener").to_string())?;
+    if header[3] == HTTP2_SETTINGS_FRAME {
+      Ok(())
+    } else {
+      Err(i18n("The node did not answer the gRPC handshake, check that the address points at its gRPC port").to_string())
+    }
+  };
+
+  match tokio::time::timeout(NODE_CHECK_TIMEOUT, check).await {
+    Ok(result) => result,
+    Err(_) => Err(i18n("Timed out connecting to the node").to_string()),
+  }
+}
+
 pub struct BridgeService {
   pub application_events: ApplicationEventsChannel,
   pub service_events: Channel<BridgeEvents>,
   pub task_ctl: Channel<()>,
   pub is_enabled: Arc<AtomicBool>,
   pub bridge_sender: Sender<DaemonMessage>,
+  log_to_file: Arc<AtomicBool>,
+  log_settings: std::sync::Mutex<LogSettings>,
+  // set when no local node runs and the bridge mines against `node_address`
+  remote_node: Arc<AtomicBool>,
+  node_address: std::sync::Mutex<String>,
 }
 
 impl BridgeService {
@@ -36,11 +184,29 @@ impl BridgeService {
       application_events,
       service_events: Channel::unbounded(),
       task_ctl: Channel::oneshot(),
-      is_enabled: Arc::new(AtomicBool::new(
-        settings.node.node_kind == WagLayladNodeKind::IntegratedAsDaemon &&
-        settings.node.enable_bridge
-      )),
+      is_enabled: Arc::new(AtomicBool::new(settings.node.enable_bridge)),
       bridge_sender,
+      log_to_file: Arc::new(AtomicBool::new(settings.bridge.log_to_file)),
+      log_settings: std::sync::Mutex::new(settings.logs.clone()),
+      remote_node: Arc::new(AtomicBool::new(!settings.node.node_kind.is_local())),
+      node_address: std::sync::Mutex::new(settings.bridge.waglayla_address.clone()),
+    }
+  }
+
+  pub fn update_settings(&self, settings: &Settings) {
+    self.log_to_file.store(settings.bridge.log_to_file, Ordering::Relaxed);
+    *self.log_settings.lock().unwrap() = settings.logs.clone();
+    *self.node_address.lock().unwrap() = settings.bridge.waglayla_address.clone();
+  }
+
+  fn rotate_log(&self, log: &mut ExternalLogFile) {
+    if !self.log_to_file.load(Ordering::Relaxed) {
+      return;
+    }
+
+    let policy = self.log_settings.lock().unwrap().clone();
+    if let Err(err) = log.check(&policy) {
+      eprintln!("Failed to rotate bridge log: {}", err);
     }
   }
 
@@ -58,6 +224,14 @@ impl BridgeService {
       .unwrap();
   }
 
+  /// Restarts a running bridge so that it picks up the current `config.yaml`
+  pub fn reconfigure(&self) {
+    self.service_events
+      .sender
+      .try_send(BridgeEvents::Reconfigure)
+      .unwrap();
+  }
+
   pub fn disable(&self) {
     self.service_events
       .sender
@@ -65,6 +239,41 @@ impl BridgeService {
       .unwrap();
   }
 
+  fn notify(&self, status: ReconfigureStatus) {
+    if let Err(e) = self.application_events.sender.try_send(Events::BridgeReconfigure(status)) {
+      log_error!("Failed to send bridge reconfigure status: {}", e);
+    }
+  }
+
+  fn notify_node(&self, status: Option<BridgeNodeStatus>) {
+    if let Err(e) = self.application_events.sender.try_send(Events::BridgeNode(status)) {
+      log_error!("Failed to send bridge node status: {}", e);
+    }
+  }
+
+  /// SIGTERM followed by SIGKILL if the bridge does not exit within [`BRIDGE_SHUTDOWN_TIMEOUT`]
+  async fn stop_gracefully(child_process: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child_process.id() {
+      use nix::sys::signal::Signal;
+      use nix::unistd::Pid;
+      if let Err(err) = nix::sys::signal::kill(Pid::from_raw(pid as i32), Signal::SIGTERM) {
+        eprintln!("bridge sigterm error: {:?}", err);
+      }
+      if tokio::time::timeout(BRIDGE_SHUTDOWN_TIMEOUT, child_process.wait()).await.is_ok() {
+        return;
+      }
+      eprintln!("bridge did not exit within {:?}, sending SIGKILL", BRIDGE_SHUTDOWN_TIMEOUT);
+    }
+
+    if let Err(err) = child_process.kill().await {
+      eprintln!("Failed to kill bridge: {:?}", err);
+    }
+    if let Err(err) = child_process.wait().await {
+      eprintln!("Failed to wait for bridge exit: {:?}", err);
+    }
+  }
+
   async fn pipe_output<R: AsyncRead + Unpin + Send + 'static>(
     reader: R,
     sender: Sender<DaemonMessage>,
@@ -82,17 +291,18 @@ impl BridgeService {
   }
 
   pub fn update_services(&self, node_settings: &NodeSettings, options: Option<RpcOptions>) {
-    match node_settings.node_kind {
-      WagLayladNodeKind::IntegratedAsDaemon => {
-        if (node_settings.enable_bridge) {
-          self.enable();
-        } else {
-          self.disable();
-        }
-      },
-      _ => {
-        self.disable();
+    // without a local node the bridge mines against the remote node set in its config
+    let remote_node = !node_settings.node_kind.is_local();
+    let switched = self.remote_node.swap(remote_node, Ordering::Relaxed) != remote_node;
+
+    if node_settings.enable_bridge {
+      // a running bridge is restarted so that the remote node is checked before it reconnects
+      if switched && self.is_enabled.load(Ordering::Relaxed) {
+        self.reconfigure();
       }
+      self.enable();
+    } else {
+      self.disable();
     }
   }
 }
@@ -123,22 +333,19 @@ impl Service for BridgeService {
   
     let bin = Storage::try_new(go_binary_name)?;
     let target_path = bin.filename();
+    bin.ensure_dir_sync()?;
   
-    if !target_path.exists() {
-      let mut temp_file = File::create(target_path)?;
-      temp_file.write_all(BINARY)?;
-  
-      #[cfg(unix)]
-      {
-        use std::os::unix::fs::PermissionsExt;
-        std::fs::set_permissions(target_path, std::fs::Permissions::from_mode(0o755))?;
-      }
-    }
-  
+    let mut bridge_log = ExternalLogFile::new(
+      target_path.parent().unwrap_or_else(|| Path::new(".")).join(BRIDGE_LOG_FILE)
+    );
+
     let mut backoff = 1;
     const MAX_BACKOFF: u64 = 16;
 
     let mut exit_requested = false;
+    // set while a reconfigured bridge is restarting, until it proves stable
+    let mut reconfiguring = false;
+    let mut startup_deadline: Option<Instant> = None;
 
     loop {
       let mut command = Command::new(&target_path);
@@ -155,18 +362,66 @@ impl Service for BridgeService {
       }
 
       if self.is_enabled.load(Ordering::Relaxed) == true {
-        let mut child_process = match
-          command.spawn()
-        {
+        self.rotate_log(&mut bridge_log);
+
+        if self.remote_node.load(Ordering::Relaxed) {
+          let address = self.node_address.lock().unwrap().clone();
+          match check_node_connection(&address).await {
+            Ok(()) => self.notify_node(Some(BridgeNodeStatus::Connected(address))),
+            Err(reason) => {
+              eprintln!("Bridge node {} is not reachable: {}. Retrying...", address, reason);
+              self.notify_node(Some(BridgeNodeStatus::Unreachable { address, reason: reason.clone() }));
+              if std::mem::take(&mut reconfiguring) {
+                self.notify(ReconfigureStatus::Failed(reason));
+              }
+
+              // the node may stay down for long, keep serving requests while waiting
+              select! {
+                msg = this.as_ref().service_events.receiver.recv().fuse() => {
+                  match msg {
+                    Ok(BridgeEvents::Enable) => {},
+                    Ok(BridgeEvents::Disable) => {
+                      self.is_enabled.store(false, Ordering::Relaxed);
+                    },
+                    Ok(BridgeEvents::Reconfigure) => {
+                      self.notify(ReconfigureStatus::Restarting);
+                      reconfiguring = true;
+                    },
+                    Ok(BridgeEvents::Exit) | Err(_) => break,
+                  }
+                }
+                _ = tokio::time::sleep(Duration::from_secs(backoff)).fuse() => {
+                  backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+              }
+              continue;
+            }
+          }
+        } else {
+          self.notify_node(None);
+        }
+
+        // verified before every launch, the binary may have been replaced or damaged on disk
+        let spawned = ensure_bridge_binary(target_path)
+          .and_then(|_| command.spawn().map_err(Error::from));
+
+        let mut child_process = match spawned {
           Ok(child) => child,
           Err(e) => {
             eprintln!("Failed to start bridge process: {}. Retrying...", e);
+            if std::mem::take(&mut reconfiguring) {
+              self.notify(ReconfigureStatus::Failed(e.to_string()));
+            }
             tokio::time::sleep(Duration::from_secs(backoff)).await;
             backoff = (backoff * 2).min(MAX_BACKOFF);
             continue;
           }
         };
 
+        if reconfiguring {
+          startup_deadline = Some(Instant::now() + BRIDGE_STARTUP_GRACE);
+        }
+
         if let Some(stdout) = child_process.stdout.take() {
           let sender = self.bridge_sender.clone();
           tokio::spawn(Self::pipe_output(stdout, sender, "stdout"));
@@ -191,6 +446,13 @@ impl Service for BridgeService {
                     let _ = child_process.wait().await.expect("Failed to wait for bridge exit");
                     break;
                   },
+                  BridgeEvents::Reconfigure => {
+                    self.notify(ReconfigureStatus::Restarting);
+                    Self::stop_gracefully(&mut child_process).await;
+                    reconfiguring = true;
+                    startup_deadline = None;
+                    break;
+                  },
                   BridgeEvents::Exit => {
                     let _ = child_process.kill().await.expect("failed to kill bridge");
                     let _ = child_process.wait().await.expect("Failed to wait for bridge exit");
@@ -203,18 +465,34 @@ impl Service for BridgeService {
               }
             }
             _ = tokio::time::sleep(Duration::from_secs(1)).fuse() => {
+              self.rotate_log(&mut bridge_log);
+
               if let Ok(Some(status)) = child_process.try_wait() {
                 if !exit_requested {
                   eprintln!("Bridge process exited with status: {}. Restarting...", status);
                 }
+                if startup_deadline.take().is_some() {
+                  reconfiguring = false;
+                  self.notify(ReconfigureStatus::Failed(format!("{} {}", i18n("exited with"), status)));
+                }
                 break;
               }
+
+              if startup_deadline.map(|deadline| Instant::now() >= deadline).unwrap_or(false) {
+                startup_deadline = None;
+                reconfiguring = false;
+                backoff = 1;
+                self.notify(ReconfigureStatus::Applied);
+              }
             }
           }
         }
 
-        tokio::time::sleep(Duration::from_secs(backoff)).await;
-        backoff = (backoff * 2).min(MAX_BACKOFF);
+        // a requested restart starts the new configuration right away
+        if !reconfiguring {
+          tokio::time::sleep(Duration::from_secs(backoff)).await;
+          backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
       } else {
         loop {
           select! {
@@ -228,6 +506,8 @@ impl Service for BridgeService {
                   BridgeEvents::Disable => {
                     self.is_enabled.store(false, Ordering::Relaxed);
                   },
+                  // a stopped bridge reads the new config when it is enabled
+                  BridgeEvents::Reconfigure => {},
                   BridgeEvents::Exit => {
                     exit_requested = true;
                     break;
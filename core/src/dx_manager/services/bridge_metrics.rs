@@ -0,0 +1,469 @@
+use crate::imports::*;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::net::{Ipv4Addr, SocketAddr};
+
+pub const BRIDGE_METRICS_POLLING_INTERVAL_SECONDS: u64 = 5;
+// one hour of samples at the polling interval
+const HASHRATE_HISTORY_LENGTH: usize = 720;
+const RECENT_BLOCKS: usize = 64;
+// the bridge accounts share work in GH; a share of difficulty 1 is worth 2^32 hashes
+const HASHES_PER_WORK_UNIT: f64 = 1e9;
+const HASHES_PER_DIFFICULTY: f64 = 4_294_967_296.0;
+
+// metric names exported by the bridge, without their prefix
+const METRIC_PREFIXES: &[&str] = &["ks_", "wala_"];
+const VALID_SHARES: &str = "valid_share_counter";
+const VALID_SHARE_WORK: &str = "valid_share_diff_counter";
+const INVALID_SHARES: &str = "invalid_share_counter";
+// counter of blocks per worker, labelled like the share counters
+const BLOCKS_MINED: &str = "blocks_mined";
+// one series per found block, carrying its hash and blue score as labels
+const MINED_BLOCKS: &str = "mined_blocks_gauge";
+
+pub enum BridgeMetricsEvents {
+  Exit,
+}
+
+/// Mining statistics of a single worker connected to the bridge
+#[derive(Debug, Clone, Default)]
+pub struct WorkerMetrics {
+  pub worker: String,
+  pub wallet: String,
+  pub hashrate: f64,
+  pub valid_shares: u64,
+  pub stale_shares: u64,
+  pub invalid_shares: u64,
+  // average difficulty of the shares accepted since the last scrape
+  pub difficulty: Option<f64>,
+  pub blocks: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FoundBlock {
+  pub worker: String,
+  pub hash: String,
+  pub blue_score: u64,
+  // `None` for blocks found before the dashboard started monitoring
+  pub found: Option<DateTime<Local>>,
+}
+
+/// Latest scrape of the bridge Prometheus endpoint
+#[derive(Debug, Clone, Default)]
+pub struct BridgeMetricsReport {
+  pub workers: Vec<WorkerMetrics>,
+  pub blocks: Vec<FoundBlock>,
+  pub total_blocks: u64,
+  pub hashrate_history: Vec<f64>,
+  pub error: Option<String>,
+  pub updated: Option<Instant>,
+}
+
+impl BridgeMetricsReport {
+  pub fn hashrate(&self) -> f64 {
+    self.workers.iter().map(|worker| worker.hashrate).sum()
+  }
+
+  pub fn valid_shares(&self) -> u64 {
+    self.workers.iter().map(|worker| worker.valid_shares).sum()
+  }
+
+  pub fn stale_shares(&self) -> u64 {
+    self.workers.iter().map(|worker| worker.stale_shares).sum()
+  }
+
+  pub fn invalid_shares(&self) -> u64 {
+    self.workers.iter().map(|worker| worker.invalid_shares).sum()
+  }
+}
+
+/// One sample of the Prometheus text exposition format
+#[derive(Debug, Clone)]
+struct Sample {
+  name: String,
+  labels: HashMap<String, String>,
+  value: f64,
+}
+
+impl Sample {
+  fn label(&self, name: &str) -> &str {
+    self.labels.get(name).map(String::as_str).unwrap_or_default()
+  }
+}
+
+fn parse_labels(labels: &str) -> HashMap<String, String> {
+  let mut map = HashMap::new();
+  let mut rest = labels;
+  while let Some((name, tail)) = rest.split_once("=\"") {
+    let mut value = String::new();
+    let mut chars = tail.char_indices();
+    let mut end = tail.len();
+    while let Some((index, c)) = chars.next() {
+      match c {
+        '\\' => match chars.next() {
+          Some((_, 'n')) => value.push('\n'),
+          Some((_, c)) => value.push(c),
+          None => {}
+        },
+        '"' => {
+          end = index + 1;
+          break;
+        }
+        c => value.push(c),
+      }
+    }
+    map.insert(name.trim_matches(|c: char| c == ',' || c.is_whitespace()).to_string(), value);
+    rest = &tail[end..];
+  }
+  map
+}
+
+fn parse_metrics(text: &str) -> Vec<Sample> {
+  text
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    .filter_map(|line| {
+      let (series, value) = match line.rfind('}') {
+        Some(index) => (&line[..=index], line[index + 1..].trim()),
+        None => line.split_once(char::is_whitespace)?,
+      };
+      // an optional timestamp follows the value
+      let value = value.split_whitespace().next()?.parse::<f64>().ok()?;
+      let (name, labels) = match series.split_once('{') {
+        Some((name, labels)) => (name, parse_labels(labels.trim_end_matches('}'))),
+        None => (series, HashMap::new()),
+      };
+      let name = METRIC_PREFIXES.iter().find_map(|prefix| name.strip_prefix(prefix)).unwrap_or(name);
+      Some(Sample { name: name.to_string(), labels, value })
+    })
+    .collect()
+}
+
+/// Metrics url of a bridge listening on `prom_port`; all interfaces are reached over the loopback
+pub fn metrics_url(prom_port: &SocketAddr) -> String {
+  let address = if prom_port.ip().is_unspecified() {
+    SocketAddr::from((Ipv4Addr::LOCALHOST, prom_port.port()))
+  } else {
+    *prom_port
+  };
+  format!("http://{address}/metrics")
+}
+
+#[derive(Default)]
+struct WorkerCounters {
+  valid_shares: f64,
+  work: f64,
+  sampled: Option<Instant>,
+  hashrate: f64,
+  difficulty: Option<f64>,
+}
+
+#[derive(Default)]
+struct State {
+  counters: HashMap<String, WorkerCounters>,
+  blocks: Vec<FoundBlock>,
+  hashrate_history: VecDeque<f64>,
+  // blocks present in the first scrape were found before monitoring started
+  primed: bool,
+}
+
+/// Scrapes the Prometheus endpoint of the embedded stratum bridge and
+/// derives per-worker hashrate, share counts and found blocks from it.
+pub struct BridgeMetricsService {
+  pub application_events: ApplicationEventsChannel,
+  pub service_events: Channel<BridgeMetricsEvents>,
+  pub task_ctl: Channel<()>,
+  url: Mutex<String>,
+  state: Mutex<State>,
+}
+
+impl BridgeMetricsService {
+  pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+    Self {
+      application_events,
+      service_events: Channel::unbounded(),
+      task_ctl: Channel::oneshot(),
+      url: Mutex::new(metrics_url(&settings.bridge.prom_port)),
+      state: Mutex::new(State::default()),
+    }
+  }
+
+  pub fn update_settings(&self, settings: &BridgeSettings) {
+    *self.url.lock().unwrap() = metrics_url(&settings.prom_port);
+  }
+
+  fn notify(&self, report: BridgeMetricsReport) {
+    if let Err(e) = self.application_events.sender.try_send(Events::BridgeMetrics(Box::new(report))) {
+      log_error!("Failed to send bridge metrics update: {}", e);
+    }
+  }
+
+  /// Folds a scrape into the running counters; counters that went
+  /// backwards mean the bridge restarted and are sampled afresh.
+  fn update(&self, samples: Vec<Sample>) -> BridgeMetricsReport {
+    let now = Instant::now();
+    let mut workers = HashMap::<String, WorkerMetrics>::new();
+    let mut totals = HashMap::<String, (f64, f64)>::new();
+    let mut blocks = Vec::new();
+
+    for sample in samples.iter() {
+      let worker = sample.label("worker").to_string();
+      let entry = workers.entry(worker.clone()).or_insert_with(|| WorkerMetrics {
+        worker: worker.clone(),
+        wallet: sample.label("wallet").to_string(),
+        ..Default::default()
+      });
+
+      match sample.name.as_str() {
+        VALID_SHARES => {
+          entry.valid_shares += sample.value as u64;
+          totals.entry(worker).or_default().0 += sample.value;
+        }
+        VALID_SHARE_WORK => {
+          totals.entry(worker).or_default().1 += sample.value;
+        }
+        INVALID_SHARES => match sample.label("type") {
+          "stale" => entry.stale_shares += sample.value as u64,
+          _ => entry.invalid_shares += sample.value as u64,
+        },
+        BLOCKS_MINED => {
+          entry.blocks += sample.value as u64;
+        }
+        MINED_BLOCKS => {
+          let hash = sample.label("hash");
+          if !hash.is_empty() {
+            blocks.push((worker, hash.to_string(), sample.label("bluescore").parse::<u64>().unwrap_or_default()));
+          }
+        }
+        _ => {}
+      }
+    }
+    // drop series that carry no mining data, e.g. network gauges
+    workers.retain(|_, worker| worker.valid_shares + worker.stale_shares + worker.invalid_shares + worker.blocks > 0);
+
+    let mut state = self.state.lock().unwrap();
+    for (worker, (valid_shares, work)) in totals {
+      let counters = state.counters.entry(worker.clone()).or_default();
+      if let Some(sampled) = counters.sampled {
+        let elapsed = now.duration_since(sampled).as_secs_f64();
+        let shares = valid_shares - counters.valid_shares;
+        let delta = work - counters.work;
+        if delta >= 0.0 && shares >= 0.0 && elapsed > 0.0 {
+          counters.hashrate = delta * HASHES_PER_WORK_UNIT / elapsed;
+          if shares > 0.0 {
+            counters.difficulty = Some(delta * HASHES_PER_WORK_UNIT / HASHES_PER_DIFFICULTY / shares);
+          }
+        } else {
+          counters.hashrate = 0.0;
+          counters.difficulty = None;
+        }
+      }
+      counters.valid_shares = valid_shares;
+      counters.work = work;
+      counters.sampled = Some(now);
+
+      if let Some(entry) = workers.get_mut(&worker) {
+        entry.hashrate = counters.hashrate;
+        entry.difficulty = counters.difficulty;
+      }
+    }
+
+    let primed = state.primed;
+    for (worker, hash, blue_score) in blocks {
+      if !state.blocks.iter().any(|block| block.hash == hash) {
+        let found = primed.then(Local::now);
+        state.blocks.push(FoundBlock { worker, hash, blue_score, found });
+      }
+    }
+    state.blocks.sort_by_key(|block| std::cmp::Reverse(block.blue_score));
+    state.primed = true;
+
+    let mut workers = workers.into_values().collect::<Vec<_>>();
+    workers.sort_by(|a, b| a.worker.cmp(&b.worker));
+
+    let hashrate = workers.iter().map(|worker| worker.hashrate).sum();
+    if state.hashrate_history.len() >= HASHRATE_HISTORY_LENGTH {
+      state.hashrate_history.pop_front();
+    }
+    state.hashrate_history.push_back(hashrate);
+
+    BridgeMetricsReport {
+      workers,
+      total_blocks: state.blocks.len() as u64,
+      blocks: state.blocks.iter().take(RECENT_BLOCKS).cloned().collect(),
+      hashrate_history: state.hashrate_history.iter().cloned().collect(),
+      error: None,
+      updated: Some(now),
+    }
+  }
+
+  async fn scrape(&self) {
+    let url = self.url.lock().unwrap().clone();
+
+    match workflow_http::get(&url).await {
+      Ok(text) => {
+        let report = self.update(parse_metrics(&text));
+        self.notify(report);
+      }
+      Err(err) => {
+        // the bridge may still be starting; keep the counters for when it answers again
+        self.notify(BridgeMetricsReport {
+          error: Some(format!("{} {}: {}", i18n("Unable to reach the bridge metrics at"), url, err)),
+          ..Default::default()
+        });
+      }
+    }
+  }
+}
+
+#[async_trait]
+impl Service for BridgeMetricsService {
+  fn name(&self) -> &'static str {
+    "bridge-metrics"
+  }
+
+  async fn launch(self: Arc<Self>) -> Result<()> {
+    let this = self.clone();
+
+    let interval = task::interval(std::time::Duration::from_secs(BRIDGE_METRICS_POLLING_INTERVAL_SECONDS));
+    pin_mut!(interval);
+
+    loop {
+      select! {
+        _ = interval.next().fuse() => {
+          if !manager().bridge_service().is_enabled.load(Ordering::Relaxed) {
+            continue;
+          }
+          this.scrape().await;
+        },
+        msg = this.as_ref().service_events.receiver.recv().fuse() => {
+          match msg {
+            Ok(BridgeMetricsEvents::Exit) | Err(_) => break,
+          }
+        }
+      }
+    }
+
+    this.task_ctl.send(()).await.unwrap();
+    Ok(())
+  }
+
+  fn terminate(self: Arc<Self>) {
+    self.service_events
+      .sender
+      .try_send(BridgeMetricsEvents::Exit)
+      .unwrap();
+  }
+
+  async fn join(self: Arc<Self>) -> Result<()> {
+    self.task_ctl.recv().await.unwrap();
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const WALLET: &str = "waglayla:qrhk7mdzw2c5vp2qs8h7rzxkrn5zxcqmmw5sxsy8f2ltmjqzvk5hxlu4yfz0e";
+
+  // scraped from the bridge /metrics endpoint, trimmed to one worker
+  fn metrics_sample() -> String {
+    [
+      "# HELP go_goroutines Number of goroutines that currently exist.",
+      "# TYPE go_goroutines gauge",
+      "go_goroutines 23",
+      "# HELP ks_blocks_mined Number of blocks mined over time",
+      "# TYPE ks_blocks_mined counter",
+      &format!("ks_blocks_mined{{ip=\"192.168.1.20:50412\",miner=\"BzMiner/v21.0.3\",wallet=\"{WALLET}\",worker=\"rig1\"}} 2"),
+      "# HELP ks_invalid_share_counter Number of stale shares found by worker over time",
+      "# TYPE ks_invalid_share_counter counter",
+      &format!("ks_invalid_share_counter{{ip=\"192.168.1.20:50412\",miner=\"BzMiner/v21.0.3\",type=\"duplicate\",wallet=\"{WALLET}\",worker=\"rig1\"}} 1"),
+      &format!("ks_invalid_share_counter{{ip=\"192.168.1.20:50412\",miner=\"BzMiner/v21.0.3\",type=\"stale\",wallet=\"{WALLET}\",worker=\"rig1\"}} 4"),
+      "# HELP ks_mined_blocks_gauge Gauge containing 1 unique instance per block mined",
+      "# TYPE ks_mined_blocks_gauge gauge",
+      &format!("ks_mined_blocks_gauge{{bluescore=\"1843021\",hash=\"7c4d0f3e2b8a1f5c9d6e0a4b3c2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d\",ip=\"192.168.1.20:50412\",miner=\"BzMiner/v21.0.3\",nonce=\"16215424683719034411\",wallet=\"{WALLET}\",worker=\"rig1\"}} 1"),
+      &format!("ks_mined_blocks_gauge{{bluescore=\"1843377\",hash=\"0a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f9\",ip=\"192.168.1.20:50412\",miner=\"BzMiner/v21.0.3\",nonce=\"902341775620183344\",wallet=\"{WALLET}\",worker=\"rig1\"}} 1"),
+      "# HELP ks_valid_share_counter Number of shares found by worker over time",
+      "# TYPE ks_valid_share_counter counter",
+      &format!("ks_valid_share_counter{{ip=\"192.168.1.20:50412\",miner=\"BzMiner/v21.0.3\",wallet=\"{WALLET}\",worker=\"rig1\"}} 1520"),
+      "# HELP ks_valid_share_diff_counter Total difficulty of shares found by worker over time",
+      "# TYPE ks_valid_share_diff_counter counter",
+      &format!("ks_valid_share_diff_counter{{ip=\"192.168.1.20:50412\",miner=\"BzMiner/v21.0.3\",wallet=\"{WALLET}\",worker=\"rig1\"}} 6.5283203125e+06"),
+      "# HELP ks_worker_job_counter Number of jobs sent to the miner by worker over time",
+      "# TYPE ks_worker_job_counter counter",
+      &format!("ks_worker_job_counter{{ip=\"192.168.1.20:50412\",miner=\"BzMiner/v21.0.3\",wallet=\"{WALLET}\",worker=\"rig1\"}} 311"),
+      "promhttp_metric_handler_requests_total{code=\"200\"} 57 1718035200000",
+    ]
+    .join("\n")
+  }
+
+  fn find<'a>(samples: &'a [Sample], name: &str, label: (&str, &str)) -> &'a Sample {
+    samples
+      .iter()
+      .find(|sample| sample.name == name && sample.label(label.0) == label.1)
+      .unwrap_or_else(|| panic!("missing {name} with {}={}", label.0, label.1))
+  }
+
+  #[test]
+  fn parse_labels_unescapes_values() {
+    let labels = parse_labels(r#"path="C:\\miners\\rig",note="say \"hi\"\nbye",empty="",worker="a,b""#);
+    assert_eq!(labels["path"], r"C:\miners\rig");
+    assert_eq!(labels["note"], "say \"hi\"\nbye");
+    assert_eq!(labels["empty"], "");
+    assert_eq!(labels["worker"], "a,b");
+    assert_eq!(labels.len(), 4);
+  }
+
+  #[test]
+  fn parse_metrics_strips_prefixes_and_skips_comments() {
+    let samples = parse_metrics(&metrics_sample());
+    assert_eq!(samples.len(), 10);
+    assert!(samples.iter().all(|sample| !sample.name.starts_with("ks_")));
+
+    let goroutines = samples.iter().find(|sample| sample.name == "go_goroutines").unwrap();
+    assert!(goroutines.labels.is_empty());
+    assert_eq!(goroutines.value, 23.0);
+
+    // the trailing timestamp is not part of the value
+    let requests = find(&samples, "promhttp_metric_handler_requests_total", ("code", "200"));
+    assert_eq!(requests.value, 57.0);
+  }
+
+  #[test]
+  fn parse_metrics_reads_worker_counters() {
+    let samples = parse_metrics(&metrics_sample());
+
+    let shares = find(&samples, VALID_SHARES, ("worker", "rig1"));
+    assert_eq!(shares.value, 1520.0);
+    assert_eq!(shares.label("wallet"), WALLET);
+    assert_eq!(shares.label("miner"), "BzMiner/v21.0.3");
+
+    assert_eq!(find(&samples, VALID_SHARE_WORK, ("worker", "rig1")).value, 6_528_320.3125);
+    assert_eq!(find(&samples, INVALID_SHARES, ("type", "stale")).value, 4.0);
+    assert_eq!(find(&samples, INVALID_SHARES, ("type", "duplicate")).value, 1.0);
+    assert_eq!(find(&samples, BLOCKS_MINED, ("worker", "rig1")).value, 2.0);
+  }
+
+  #[test]
+  fn parse_metrics_reads_block_details() {
+    let samples = parse_metrics(&metrics_sample());
+
+    let blocks = samples.iter().filter(|sample| sample.name == MINED_BLOCKS).collect::<Vec<_>>();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].label("hash"), "7c4d0f3e2b8a1f5c9d6e0a4b3c2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d");
+    assert_eq!(blocks[0].label("bluescore"), "1843021");
+    assert_eq!(blocks[1].label("bluescore"), "1843377");
+    assert!(blocks.iter().all(|block| block.label("worker") == "rig1"));
+
+    // the per-worker counter carries no block details
+    assert_eq!(find(&samples, BLOCKS_MINED, ("worker", "rig1")).label("hash"), "");
+  }
+
+  #[test]
+  fn metrics_url_uses_loopback_for_unspecified_address() {
+    assert_eq!(metrics_url(&"0.0.0.0:2114".parse().unwrap()), "http://127.0.0.1:2114/metrics");
+    assert_eq!(metrics_url(&"192.168.1.5:2114".parse().unwrap()), "http://192.168.1.5:2114/metrics");
+  }
+}
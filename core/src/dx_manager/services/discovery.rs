@@ -0,0 +1,320 @@
+use crate::imports::*;
+use super::failover::{EndpointHealth, FailoverMonitorService};
+use futures::future::{abortable, AbortHandle};
+use futures::stream::FuturesUnordered;
+use rand::seq::SliceRandom;
+use std::net::SocketAddr;
+use waglayla_wallet_core::storage::local::storage::Storage as LocalStorage;
+
+const MAINNET_DNS_SEEDERS: &[&str] = &[
+  "seeder1-mainnet.waglayla.com",
+  "seeder2-mainnet.waglayla.com",
+  "seeder3-mainnet.waglayla.com",
+];
+const SEEDER_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_CANDIDATES: usize = 24;
+const DISCOVERY_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+// number of known-good nodes kept on disk per network
+const CACHE_CAPACITY: usize = 16;
+
+pub enum NodeDiscoveryEvents {
+  Discover { network: Network, proxy: Option<ProxySettings> },
+  Cancel,
+  Exit,
+}
+
+/// Progress of a public node discovery as reported by the [`NodeDiscoveryService`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscoveryStatus {
+  Resolving,
+  Probing { probed: usize, total: usize, healthy: usize },
+  Retrying { attempt: u32 },
+  Found { url: String, latency: Duration },
+  Failed,
+  Cancelled,
+}
+
+impl std::fmt::Display for DiscoveryStatus {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      DiscoveryStatus::Resolving => write!(f, "{}", i18n("Querying seeders...")),
+      DiscoveryStatus::Probing { probed, total, .. } => write!(f, "{} {}/{}", i18n("Probing public nodes..."), probed, total),
+      DiscoveryStatus::Retrying { attempt } => write!(f, "{} ({}/{})", i18n("Retrying node discovery..."), attempt, DISCOVERY_ATTEMPTS),
+      DiscoveryStatus::Found { url, latency } => write!(f, "{} {} ({} ms)", i18n("Found public node"), url, latency.as_millis()),
+      DiscoveryStatus::Failed => write!(f, "{}", i18n("No public node available")),
+      DiscoveryStatus::Cancelled => write!(f, "{}", i18n("Node discovery cancelled")),
+    }
+  }
+}
+
+impl DiscoveryStatus {
+  pub fn is_active(&self) -> bool {
+    matches!(self, DiscoveryStatus::Resolving | DiscoveryStatus::Probing { .. } | DiscoveryStatus::Retrying { .. })
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CachedNode {
+  url: String,
+  latency_ms: u64,
+}
+
+fn seeders(network: Network) -> &'static [&'static str] {
+  match network {
+    Network::Mainnet => MAINNET_DNS_SEEDERS,
+    _ => &[],
+  }
+}
+
+fn cache_storage(network: Network) -> Result<LocalStorage> {
+  Ok(LocalStorage::try_new(&format!("public-nodes-{network}.json"))?)
+}
+
+async fn load_cache(network: Network) -> Vec<CachedNode> {
+  let Ok(storage) = cache_storage(network) else {
+    return Vec::new();
+  };
+  if !storage.exists().await.unwrap_or(false) {
+    return Vec::new();
+  }
+  workflow_store::fs::read_json::<Vec<CachedNode>>(storage.filename()).await.unwrap_or_else(|err| {
+    log_warn!("unable to read the public node cache: {err}");
+    Vec::new()
+  })
+}
+
+async fn store_cache(network: Network, nodes: &[CachedNode]) -> Result<()> {
+  let storage = cache_storage(network)?;
+  storage.ensure_dir().await?;
+  workflow_store::fs::write_json(storage.filename(), &nodes).await?;
+  Ok(())
+}
+
+/// Candidate addresses from the DNS seeders of the network. Behind a proxy with
+/// remote DNS the seeders are not resolved locally; each connection to a seeder
+/// name is resolved by the proxy to one of the nodes it lists instead.
+async fn seed_candidates(network: Network, proxy: Option<&ProxySettings>) -> Vec<String> {
+  let port = NetworkId::from(network).default_borsh_rpc_port();
+  if proxy.map(|proxy| proxy.remote_dns).unwrap_or(false) {
+    return seeders(network).iter().map(|seeder| format!("{seeder}:{port}")).collect();
+  }
+
+  resolve_seeders(network, port).await.into_iter().map(|address| address.to_string()).collect()
+}
+
+/// Resolves the DNS seeders of the network concurrently, each bounded by [`SEEDER_TIMEOUT`]
+async fn resolve_seeders(network: Network, port: u16) -> Vec<SocketAddr> {
+  let lookups = seeders(network).iter().map(|seeder| async move {
+    match tokio::time::timeout(SEEDER_TIMEOUT, tokio::net::lookup_host((*seeder, port))).await {
+      Ok(Ok(addresses)) => addresses.collect::<Vec<_>>(),
+      Ok(Err(err)) => {
+        log_warn!("unable to resolve seeder {seeder}: {err}");
+        Vec::new()
+      }
+      Err(_) => {
+        log_warn!("seeder {seeder} did not respond");
+        Vec::new()
+      }
+    }
+  });
+
+  let mut addresses = futures::future::join_all(lookups).await.into_iter().flatten().collect::<Vec<_>>();
+  addresses.shuffle(&mut rand::thread_rng());
+  addresses
+}
+
+/// Finds a public node to connect to in the background. Cached known-good
+/// nodes and seeder addresses are probed in parallel and ranked by sync state
+/// and latency; the best one is handed to the [`WagLaylaService`].
+pub struct NodeDiscoveryService {
+  pub application_events: ApplicationEventsChannel,
+  pub service_events: Channel<NodeDiscoveryEvents>,
+  pub task_ctl: Channel<()>,
+  abort: Mutex<Option<AbortHandle>>,
+  // bumped whenever a discovery is started or cancelled
+  generation: AtomicU64,
+}
+
+impl NodeDiscoveryService {
+  pub fn new(application_events: ApplicationEventsChannel, _settings: &Settings) -> Self {
+    Self {
+      application_events,
+      service_events: Channel::unbounded(),
+      task_ctl: Channel::oneshot(),
+      abort: Mutex::new(None),
+      generation: AtomicU64::new(0),
+    }
+  }
+
+  /// Starts a discovery, replacing the one in progress
+  pub fn discover(&self, network: Network, proxy: Option<ProxySettings>) {
+    self.service_events
+      .sender
+      .try_send(NodeDiscoveryEvents::Discover { network, proxy })
+      .unwrap_or_else(|err| {
+        log_error!("NodeDiscoveryService error: {}", err);
+      });
+  }
+
+  /// Stops the discovery in progress, if any
+  pub fn cancel(&self) {
+    self.service_events
+      .sender
+      .try_send(NodeDiscoveryEvents::Cancel)
+      .unwrap_or_else(|err| {
+        log_error!("NodeDiscoveryService error: {}", err);
+      });
+  }
+
+  fn abort(&self) -> bool {
+    let mut abort = self.abort.lock().unwrap();
+    self.generation.fetch_add(1, Ordering::SeqCst);
+    if let Some(handle) = abort.take() {
+      handle.abort();
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Releases the abort handle unless a newer discovery has replaced it
+  fn finish(&self, generation: u64) {
+    let mut abort = self.abort.lock().unwrap();
+    if self.generation.load(Ordering::SeqCst) == generation {
+      abort.take();
+    }
+  }
+
+  fn notify(&self, status: DiscoveryStatus) {
+    if let Err(e) = self.application_events.sender.try_send(Events::NodeDiscovery(status)) {
+      log_error!("Failed to send node discovery update: {}", e);
+    }
+  }
+
+  async fn probe_all(&self, network: Network, proxy: Option<&ProxySettings>, candidates: Vec<String>) -> Vec<(String, EndpointHealth, Duration)> {
+    let total = candidates.len();
+    let mut probes = candidates
+      .into_iter()
+      .map(|url| async move {
+        let start = Instant::now();
+        let health = FailoverMonitorService::probe(url.clone(), WrpcEncoding::Borsh, network, TlsTrust::default(), proxy.cloned()).await;
+        (url, health, start.elapsed())
+      })
+      .collect::<FuturesUnordered<_>>();
+
+    let mut results = Vec::with_capacity(total);
+    self.notify(DiscoveryStatus::Probing { probed: 0, total, healthy: 0 });
+    while let Some(result) = probes.next().await {
+      results.push(result);
+      let healthy = results.iter().filter(|(_, health, _)| health.is_healthy()).count();
+      self.notify(DiscoveryStatus::Probing { probed: results.len(), total, healthy });
+    }
+    results
+  }
+
+  async fn run(self: Arc<Self>, network: Network, proxy: Option<ProxySettings>, generation: u64) {
+    for attempt in 1..=DISCOVERY_ATTEMPTS {
+      if attempt > 1 {
+        self.notify(DiscoveryStatus::Retrying { attempt });
+        task::sleep(RETRY_DELAY).await;
+      }
+
+      self.notify(DiscoveryStatus::Resolving);
+      let cache = load_cache(network).await;
+      let mut candidates = cache.iter().map(|node| node.url.clone()).collect::<Vec<_>>();
+      for url in seed_candidates(network, proxy.as_ref()).await {
+        if !candidates.contains(&url) {
+          candidates.push(url);
+        }
+      }
+      candidates.truncate(MAX_CANDIDATES);
+      if candidates.is_empty() {
+        continue;
+      }
+
+      // synced nodes first, then nodes still syncing, each ordered by latency
+      let mut ranked = self
+        .probe_all(network, proxy.as_ref(), candidates)
+        .await
+        .into_iter()
+        .filter(|(_, health, _)| matches!(health, EndpointHealth::Healthy | EndpointHealth::NotSynced))
+        .collect::<Vec<_>>();
+      ranked.sort_by_key(|(_, health, latency)| (!health.is_healthy(), *latency));
+
+      let Some((url, _, latency)) = ranked.first().cloned() else {
+        continue;
+      };
+
+      let known_good = ranked
+        .iter()
+        .filter(|(_, health, _)| health.is_healthy())
+        .take(CACHE_CAPACITY)
+        .map(|(url, _, latency)| CachedNode { url: url.clone(), latency_ms: latency.as_millis() as u64 })
+        .collect::<Vec<_>>();
+      if !known_good.is_empty() {
+        if let Err(err) = store_cache(network, &known_good).await {
+          log_warn!("unable to store the public node cache: {err}");
+        }
+      }
+
+      self.finish(generation);
+      self.notify(DiscoveryStatus::Found { url: url.clone(), latency });
+      manager().waglayla_service().connect_public_node(url, network, proxy);
+      return;
+    }
+
+    self.finish(generation);
+    self.notify(DiscoveryStatus::Failed);
+  }
+}
+
+#[async_trait]
+impl Service for NodeDiscoveryService {
+  fn name(&self) -> &'static str {
+    "node-discovery"
+  }
+
+  async fn launch(self: Arc<Self>) -> Result<()> {
+    let this = self.clone();
+
+    loop {
+      match this.service_events.receiver.recv().await {
+        Ok(NodeDiscoveryEvents::Discover { network, proxy }) => {
+          this.abort();
+          let generation = this.generation.load(Ordering::SeqCst);
+          let (discovery, handle) = abortable(this.clone().run(network, proxy, generation));
+          this.abort.lock().unwrap().replace(handle);
+          task::spawn(async move {
+            discovery.await.ok();
+          });
+        }
+        Ok(NodeDiscoveryEvents::Cancel) => {
+          if this.abort() {
+            this.notify(DiscoveryStatus::Cancelled);
+          }
+        }
+        Ok(NodeDiscoveryEvents::Exit) | Err(_) => {
+          this.abort();
+          break;
+        }
+      }
+    }
+
+    this.task_ctl.send(()).await.unwrap();
+    Ok(())
+  }
+
+  fn terminate(self: Arc<Self>) {
+    self.service_events
+      .sender
+      .try_send(NodeDiscoveryEvents::Exit)
+      .unwrap();
+  }
+
+  async fn join(self: Arc<Self>) -> Result<()> {
+    self.task_ctl.recv().await.unwrap();
+    Ok(())
+  }
+}
@@ -0,0 +1,341 @@
+use crate::imports::*;
+use std::collections::VecDeque;
+use waglayla_wallet_core::rpc::{ConnectOptions, ConnectStrategy};
+#[cfg(not(target_arch = "wasm32"))]
+use super::waglayla::tunnel::RemoteEndpoint;
+
+pub const FAILOVER_POLLING_INTERVAL_SECONDS: u64 = 5; // 5 sec
+const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+// time the active endpoint may stay disconnected before switching
+const DISCONNECT_GRACE_PERIOD: Duration = Duration::from_secs(15);
+// time given to a newly selected endpoint to connect
+const SWITCH_COOLDOWN: Duration = Duration::from_secs(30);
+const FAILOVER_HISTORY_LENGTH: usize = 32;
+
+pub enum FailoverMonitorEvents {
+  Exit,
+}
+
+/// Result of the last probe of a remote wRPC endpoint
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub enum EndpointHealth {
+  #[default]
+  Unknown,
+  Healthy,
+  Unreachable(String),
+  NotSynced,
+  WrongNetwork(String),
+  NoUtxoIndex,
+}
+
+impl std::fmt::Display for EndpointHealth {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      EndpointHealth::Unknown => write!(f, "{}", i18n("Not probed")),
+      EndpointHealth::Healthy => write!(f, "{}", i18n("Healthy")),
+      EndpointHealth::Unreachable(err) => write!(f, "{}: {}", i18n("Unreachable"), err),
+      EndpointHealth::NotSynced => write!(f, "{}", i18n("Not synced")),
+      EndpointHealth::WrongNetwork(network) => write!(f, "{}: {}", i18n("Wrong network"), network),
+      EndpointHealth::NoUtxoIndex => write!(f, "{}", i18n("UTXO index not enabled")),
+    }
+  }
+}
+
+impl EndpointHealth {
+  pub fn is_healthy(&self) -> bool {
+    matches!(self, EndpointHealth::Healthy)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct EndpointStatus {
+  pub url: String,
+  pub health: EndpointHealth,
+  pub checked: Option<Instant>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FailoverRecord {
+  pub time: Instant,
+  pub from: Option<String>,
+  pub to: String,
+  pub reason: String,
+}
+
+/// Snapshot of the remote endpoint list shown in the Network tab
+#[derive(Default, Debug, Clone)]
+pub struct FailoverReport {
+  pub active: Option<String>,
+  pub endpoints: Vec<EndpointStatus>,
+  pub history: VecDeque<FailoverRecord>,
+}
+
+#[derive(Default)]
+struct State {
+  report: FailoverReport,
+  last_probe: Option<Instant>,
+  disconnected_since: Option<Instant>,
+  last_switch: Option<Instant>,
+}
+
+/// Probes the configured remote wRPC endpoints and requests a switch to the
+/// next healthy endpoint when the active one disconnects or falls behind.
+pub struct FailoverMonitorService {
+  pub application_events: ApplicationEventsChannel,
+  pub service_events: Channel<FailoverMonitorEvents>,
+  pub task_ctl: Channel<()>,
+  settings: Mutex<NodeSettings>,
+  state: Mutex<State>,
+  is_reported: AtomicBool,
+}
+
+impl FailoverMonitorService {
+  pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+    let this = Self {
+      application_events,
+      service_events: Channel::unbounded(),
+      task_ctl: Channel::oneshot(),
+      settings: Mutex::new(settings.node.clone()),
+      state: Mutex::new(State::default()),
+      is_reported: AtomicBool::new(false),
+    };
+    this.reset_endpoints();
+    this
+  }
+
+  pub fn update_settings(&self, node_settings: &NodeSettings) {
+    *self.settings.lock().unwrap() = node_settings.clone();
+    self.reset_endpoints();
+  }
+
+  pub fn report(&self) -> FailoverReport {
+    self.state.lock().unwrap().report.clone()
+  }
+
+  fn reset_endpoints(&self) {
+    let endpoints = self.settings.lock().unwrap().remote_endpoints();
+    let mut state = self.state.lock().unwrap();
+    let previous = std::mem::take(&mut state.report.endpoints);
+    state.report.endpoints = endpoints
+      .into_iter()
+      .map(|url| {
+        previous.iter().find(|status| status.url == url).cloned().unwrap_or(EndpointStatus {
+          url,
+          health: EndpointHealth::Unknown,
+          checked: None,
+        })
+      })
+      .collect();
+    state.last_probe = None;
+  }
+
+  /// Failover applies to custom remote connections with more than one endpoint
+  fn is_active(&self) -> bool {
+    let settings = self.settings.lock().unwrap();
+    settings.node_kind == WagLayladNodeKind::Remote
+      && settings.connection_config_kind == NodeConnectionConfigKind::Custom
+      && settings.remote_endpoints().len() > 1
+  }
+
+  /// Connects to the endpoint and checks its network, UTXO index and sync state
+  pub(crate) async fn probe(url: String, encoding: WrpcEncoding, network: Network, trust: TlsTrust, proxy: Option<ProxySettings>) -> EndpointHealth {
+    let probe = async {
+      let network_id = NetworkId::from(network);
+      let url = WaglaylaRpcClient::parse_url(url, encoding, network_id.into())?;
+
+      // endpoints with a custom CA, a pinned certificate or a proxy are probed through a tunnel
+      #[cfg(not(target_arch = "wasm32"))]
+      let (url, _tunnel) = if RemoteEndpoint::requires_tunnel(&url, &trust, proxy.as_ref()) {
+        let tunnel = RemoteEndpoint::try_new(&url, &trust, proxy)?.tunnel().await?;
+        (tunnel.url(), Some(tunnel))
+      } else {
+        (url, None)
+      };
+      #[cfg(target_arch = "wasm32")]
+      let _ = (trust, proxy);
+
+      let client = WaglaylaRpcClient::new_with_args(encoding, Some(url.as_str()), None, Some(network_id), None)?;
+      let options = ConnectOptions {
+        block_async_connect: true,
+        strategy: ConnectStrategy::Fallback,
+        url: None,
+        connect_timeout: Some(PROBE_TIMEOUT),
+        retry_interval: None,
+      };
+      client.connect(Some(options)).await?;
+      let info = client.get_server_info().await;
+      client.disconnect().await.ok();
+      let info = info?;
+
+      let health = if info.network_id != network_id {
+        EndpointHealth::WrongNetwork(info.network_id.to_string())
+      } else if !info.has_utxo_index {
+        EndpointHealth::NoUtxoIndex
+      } else if !info.is_synced {
+        EndpointHealth::NotSynced
+      } else {
+        EndpointHealth::Healthy
+      };
+      Ok::<_, Error>(health)
+    };
+
+    select! {
+      health = probe.fuse() => health.unwrap_or_else(|err| EndpointHealth::Unreachable(err.to_string())),
+      _ = task::sleep(PROBE_TIMEOUT * 2).fuse() => EndpointHealth::Unreachable(i18n("timeout").to_string()),
+    }
+  }
+
+  async fn probe_all(&self) {
+    let (encoding, network, trust, proxy) = {
+      let settings = self.settings.lock().unwrap();
+      (settings.wrpc_encoding, settings.network, settings.tls_trust(), settings.remote_proxy())
+    };
+    let urls = self.state.lock().unwrap().report.endpoints.iter().map(|status| status.url.clone()).collect::<Vec<_>>();
+    let results = futures::future::join_all(urls.into_iter().map(|url| Self::probe(url, encoding, network, trust.clone(), proxy.clone()))).await;
+
+    let mut state = self.state.lock().unwrap();
+    let now = Instant::now();
+    // the endpoint list may have been replaced while probing
+    if state.report.endpoints.len() == results.len() {
+      for (status, health) in state.report.endpoints.iter_mut().zip(results) {
+        status.health = health;
+        status.checked = Some(now);
+      }
+    }
+    state.last_probe = Some(now);
+  }
+
+  /// Picks the endpoint to switch to along with the reason, if the active one is unusable
+  fn select_endpoint(&self) -> Option<(String, String)> {
+    let mut state = self.state.lock().unwrap();
+    let active = manager().url();
+    state.report.active = active.clone();
+
+    if state.last_switch.map(|time| time.elapsed() < SWITCH_COOLDOWN).unwrap_or(false) {
+      return None;
+    }
+
+    let active_health = state.report.endpoints.iter().find(|status| Some(&status.url) == active.as_ref()).map(|status| status.health.clone());
+    let reason = if state.disconnected_since.map(|since| since.elapsed() >= DISCONNECT_GRACE_PERIOD).unwrap_or(false) {
+      i18n("disconnected").to_string()
+    } else {
+      match active_health {
+        Some(health) if !health.is_healthy() && health != EndpointHealth::Unknown => health.to_string(),
+        _ => return None,
+      }
+    };
+
+    let next = state.report.endpoints
+      .iter()
+      .find(|status| status.health.is_healthy() && Some(&status.url) != active.as_ref())?
+      .url
+      .clone();
+
+    state.report.history.push_front(FailoverRecord {
+      time: Instant::now(),
+      from: active,
+      to: next.clone(),
+      reason: reason.clone(),
+    });
+    state.report.history.truncate(FAILOVER_HISTORY_LENGTH);
+    state.last_switch = Some(Instant::now());
+    state.disconnected_since = None;
+
+    Some((next, reason))
+  }
+
+  fn notify(&self, event: Events) {
+    if let Err(e) = self.application_events.sender.try_send(event) {
+      log_error!("Failed to send failover update: {}", e);
+    }
+  }
+}
+
+#[async_trait]
+impl Service for FailoverMonitorService {
+  fn name(&self) -> &'static str {
+    "failover-monitor"
+  }
+
+  async fn rpc_attach(self: Arc<Self>, _rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+    // a fresh client has not connected yet
+    self.state.lock().unwrap().disconnected_since = Some(Instant::now());
+    Ok(())
+  }
+
+  async fn rpc_detach(self: Arc<Self>) -> Result<()> {
+    let mut state = self.state.lock().unwrap();
+    state.disconnected_since = None;
+    state.report.active = None;
+    Ok(())
+  }
+
+  async fn rpc_connect(self: Arc<Self>) -> Result<()> {
+    self.state.lock().unwrap().disconnected_since = None;
+    Ok(())
+  }
+
+  async fn rpc_disconnect(self: Arc<Self>) -> Result<()> {
+    self.state.lock().unwrap().disconnected_since.get_or_insert(Instant::now());
+    Ok(())
+  }
+
+  async fn launch(self: Arc<Self>) -> Result<()> {
+    let this = self.clone();
+
+    let interval = task::interval(Duration::from_secs(FAILOVER_POLLING_INTERVAL_SECONDS));
+    pin_mut!(interval);
+
+    loop {
+      select! {
+        _ = interval.next().fuse() => {
+          if !this.is_active() || manager().url().is_none() {
+            // clear the endpoint list shown for a previous configuration
+            if this.is_reported.swap(false, Ordering::Relaxed) {
+              this.notify(Events::RemoteEndpoints(Box::default()));
+            }
+            continue;
+          }
+
+          let probe_due = {
+            let state = this.state.lock().unwrap();
+            let disconnected = state.disconnected_since.map(|since| since.elapsed() >= DISCONNECT_GRACE_PERIOD).unwrap_or(false);
+            state.last_probe.map(|time| time.elapsed() >= PROBE_INTERVAL || (disconnected && time.elapsed() >= PROBE_TIMEOUT)).unwrap_or(true)
+          };
+
+          if probe_due {
+            this.probe_all().await;
+          }
+
+          let switch = this.select_endpoint();
+          this.is_reported.store(true, Ordering::Relaxed);
+          this.notify(Events::RemoteEndpoints(Box::new(this.report())));
+          if let Some((url, reason)) = switch {
+            this.notify(Events::RemoteFailover { url, reason });
+          }
+        },
+        msg = this.as_ref().service_events.receiver.recv().fuse() => {
+          match msg {
+            Ok(FailoverMonitorEvents::Exit) | Err(_) => break,
+          }
+        }
+      }
+    }
+
+    this.task_ctl.send(()).await.unwrap();
+    Ok(())
+  }
+
+  fn terminate(self: Arc<Self>) {
+    self.service_events
+      .sender
+      .try_send(FailoverMonitorEvents::Exit)
+      .unwrap();
+  }
+
+  async fn join(self: Arc<Self>) -> Result<()> {
+    self.task_ctl.recv().await.unwrap();
+    Ok(())
+  }
+}
@@ -0,0 +1,315 @@
+use crate::imports::*;
+
+pub const HEALTH_POLLING_INTERVAL_SECONDS: u64 = 5; // 5 sec
+// time given to the node to recover after a remediation before the next one
+const REMEDIATION_GRACE_PERIOD: Duration = Duration::from_secs(120);
+
+pub enum HealthMonitorEvents {
+  Enable,
+  Disable,
+  Exit,
+}
+
+/// Node health as observed by the [`HealthMonitorService`]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthState {
+  #[default]
+  Unknown,
+  Healthy,
+  Stalled,
+  NoPeers,
+  Unresponsive,
+  Recovering(HealthRemediation),
+}
+
+impl std::fmt::Display for HealthState {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      HealthState::Unknown => write!(f, "{}", i18n("Unknown")),
+      HealthState::Healthy => write!(f, "{}", i18n("Healthy")),
+      HealthState::Stalled => write!(f, "{}", i18n("Node Stalled")),
+      HealthState::NoPeers => write!(f, "{}", i18n("No Peers")),
+      HealthState::Unresponsive => write!(f, "{}", i18n("Node Unresponsive")),
+      HealthState::Recovering(_) => write!(f, "{}", i18n("Recovering...")),
+    }
+  }
+}
+
+impl HealthState {
+  pub fn is_problem(&self) -> bool {
+    matches!(self, HealthState::Stalled | HealthState::NoPeers | HealthState::Unresponsive)
+  }
+
+  pub fn describe(&self) -> Option<String> {
+    match self {
+      HealthState::Stalled => Some(i18n("The node has not made any sync progress").to_string()),
+      HealthState::NoPeers => Some(i18n("The node has no connected peers").to_string()),
+      HealthState::Unresponsive => Some(i18n("The node does not respond to RPC requests").to_string()),
+      HealthState::Recovering(remediation) => Some(format!("{}: {}", i18n("Remediation in progress"), remediation)),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Default)]
+struct Sample {
+  // (virtual DAA score, header count, block count); `None` if the node did not respond
+  progress: Option<(u64, u64, u64)>,
+  peers: Option<usize>,
+  is_synced: Option<bool>,
+}
+
+struct Monitor {
+  state: HealthState,
+  progress: Option<(u64, u64, u64)>,
+  last_progress: Instant,
+  responsive: bool,
+  peers_lost: Option<Instant>,
+  remediation: Option<(HealthRemediation, Instant)>,
+  attempts: u32,
+}
+
+impl Default for Monitor {
+  fn default() -> Self {
+    Self {
+      state: HealthState::Unknown,
+      progress: None,
+      last_progress: Instant::now(),
+      responsive: true,
+      peers_lost: None,
+      remediation: None,
+      attempts: 0,
+    }
+  }
+}
+
+impl Monitor {
+  // a new RPC connection restarts the timers but keeps the remediation count
+  fn reset_timers(&mut self) {
+    self.progress = None;
+    self.last_progress = Instant::now();
+    self.responsive = true;
+    self.peers_lost = None;
+  }
+
+  fn evaluate(&mut self, sample: Sample, settings: &HealthSettings) -> (HealthState, Option<HealthRemediation>) {
+    let now = Instant::now();
+
+    self.responsive = sample.progress.is_some();
+    if sample.progress.is_some() && sample.progress != self.progress {
+      self.progress = sample.progress;
+      self.last_progress = now;
+    }
+
+    // a stall only matters while the node is catching up; once synced it merely
+    // follows the network, which may go quiet without anything being wrong
+    if sample.progress.is_some() && sample.is_synced == Some(true) {
+      self.last_progress = now;
+    }
+
+    match sample.peers {
+      Some(0) => {
+        self.peers_lost.get_or_insert(now);
+      }
+      Some(_) => self.peers_lost = None,
+      None => {}
+    }
+
+    let stall_timeout = Duration::from_secs(settings.stall_timeout_minutes * 60);
+    let no_peers_timeout = Duration::from_secs(settings.no_peers_timeout_minutes * 60);
+
+    let problem = if now.duration_since(self.last_progress) >= stall_timeout {
+      if self.responsive { HealthState::Stalled } else { HealthState::Unresponsive }
+    } else if self.peers_lost.map(|since| now.duration_since(since) >= no_peers_timeout).unwrap_or(false) {
+      HealthState::NoPeers
+    } else {
+      HealthState::Healthy
+    };
+
+    if problem == HealthState::Healthy {
+      self.remediation = None;
+      self.attempts = 0;
+      return (HealthState::Healthy, None);
+    }
+
+    if let Some((remediation, started)) = self.remediation {
+      if now.duration_since(started) < REMEDIATION_GRACE_PERIOD {
+        return (HealthState::Recovering(remediation), None);
+      }
+    }
+
+    if settings.remediation != HealthRemediation::Notify && self.attempts < settings.max_remediations {
+      self.attempts += 1;
+      self.remediation = Some((settings.remediation, now));
+      self.reset_timers();
+      return (HealthState::Recovering(settings.remediation), Some(settings.remediation));
+    }
+
+    (problem, None)
+  }
+}
+
+/// Tracks the sync progress and peer count of the connected node and
+/// requests a remediation when the node stops making progress.
+pub struct HealthMonitorService {
+  pub application_events: ApplicationEventsChannel,
+  pub service_events: Channel<HealthMonitorEvents>,
+  pub task_ctl: Channel<()>,
+  pub rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
+  pub is_enabled: Arc<AtomicBool>,
+  settings: Mutex<HealthSettings>,
+  monitor: Mutex<Monitor>,
+}
+
+impl HealthMonitorService {
+  pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+    Self {
+      application_events,
+      service_events: Channel::unbounded(),
+      task_ctl: Channel::oneshot(),
+      rpc_api: Mutex::new(None),
+      is_enabled: Arc::new(AtomicBool::new(settings.health.enable)),
+      settings: Mutex::new(settings.health.clone()),
+      monitor: Mutex::new(Monitor::default()),
+    }
+  }
+
+  pub fn rpc_api(&self) -> Option<Arc<dyn RpcApi>> {
+    self.rpc_api.lock().unwrap().clone()
+  }
+
+  pub fn enable(&self) {
+    self.service_events
+      .sender
+      .try_send(HealthMonitorEvents::Enable)
+      .unwrap();
+  }
+
+  pub fn disable(&self) {
+    self.service_events
+      .sender
+      .try_send(HealthMonitorEvents::Disable)
+      .unwrap();
+  }
+
+  pub fn update_settings(&self, settings: &HealthSettings) {
+    *self.settings.lock().unwrap() = settings.clone();
+    if settings.enable {
+      self.enable();
+    } else {
+      self.disable();
+    }
+  }
+
+  async fn sample(rpc_api: &Arc<dyn RpcApi>) -> Sample {
+    let progress = rpc_api
+      .get_block_dag_info()
+      .await
+      .ok()
+      .map(|info| (info.virtual_daa_score, info.header_count, info.block_count));
+
+    // public nodes may refuse peer queries, in which case peers are not tracked
+    let peers = rpc_api
+      .get_connected_peer_info()
+      .await
+      .ok()
+      .map(|resp| resp.peer_info.len());
+
+    let is_synced = rpc_api.get_sync_status().await.ok();
+
+    Sample { progress, peers, is_synced }
+  }
+
+  fn set_state(&self, state: HealthState, remediation: Option<HealthRemediation>) {
+    let changed = {
+      let mut monitor = self.monitor.lock().unwrap();
+      let changed = monitor.state != state;
+      monitor.state = state;
+      changed
+    };
+
+    if changed || remediation.is_some() {
+      if let Err(e) = self.application_events.sender.try_send(Events::NodeHealth { state, remediation }) {
+        log_error!("Failed to send node health update: {}", e);
+      }
+    }
+  }
+}
+
+#[async_trait]
+impl Service for HealthMonitorService {
+  fn name(&self) -> &'static str {
+    "health-monitor"
+  }
+
+  async fn rpc_attach(self: Arc<Self>, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+    self.rpc_api.lock().unwrap().replace(rpc_api.clone());
+    self.monitor.lock().unwrap().reset_timers();
+    Ok(())
+  }
+
+  async fn rpc_detach(self: Arc<Self>) -> Result<()> {
+    self.rpc_api.lock().unwrap().take();
+    self.set_state(HealthState::Unknown, None);
+    Ok(())
+  }
+
+  async fn launch(self: Arc<Self>) -> Result<()> {
+    let this = self.clone();
+
+    let interval = task::interval(Duration::from_secs(HEALTH_POLLING_INTERVAL_SECONDS));
+    pin_mut!(interval);
+
+    loop {
+      select! {
+        _ = interval.next().fuse() => {
+          if !self.is_enabled.load(Ordering::Relaxed) {
+            continue;
+          }
+
+          if let Some(rpc_api) = this.rpc_api() {
+            let sample = Self::sample(&rpc_api).await;
+            let settings = this.settings.lock().unwrap().clone();
+            let (state, remediation) = this.monitor.lock().unwrap().evaluate(sample, &settings);
+            this.set_state(state, remediation);
+          }
+        },
+        msg = this.as_ref().service_events.receiver.recv().fuse() => {
+          if let Ok(event) = msg {
+            match event {
+              HealthMonitorEvents::Enable => {
+                if !self.is_enabled.swap(true, Ordering::Relaxed) {
+                  self.monitor.lock().unwrap().reset_timers();
+                }
+              }
+              HealthMonitorEvents::Disable => {
+                self.is_enabled.store(false, Ordering::Relaxed);
+                self.set_state(HealthState::Unknown, None);
+              }
+              HealthMonitorEvents::Exit => {
+                break;
+              }
+            }
+          } else {
+            break;
+          }
+        }
+      }
+    }
+
+    this.task_ctl.send(()).await.unwrap();
+    Ok(())
+  }
+
+  fn terminate(self: Arc<Self>) {
+    self.service_events
+      .sender
+      .try_send(HealthMonitorEvents::Exit)
+      .unwrap();
+  }
+
+  async fn join(self: Arc<Self>) -> Result<()> {
+    self.task_ctl.recv().await.unwrap();
+    Ok(())
+  }
+}
@@ -2,16 +2,29 @@ use crate::imports::*;
 
 pub mod waglayla;
 pub use waglayla::WagLaylaService;
+pub use waglayla::verify::NodeIncompatibility;
 
 pub mod peers;
 pub use peers::PeerMonitorService;
 
 pub mod bridge;
-pub use bridge::BridgeService;
+pub use bridge::{BridgeNodeStatus, BridgeService, ReconfigureStatus};
+
+pub mod bridge_metrics;
+pub use bridge_metrics::{BridgeMetricsReport, BridgeMetricsService};
 
 pub mod stats;
 pub use stats::StatMonitorService;
 
+pub mod health;
+pub use health::{HealthMonitorService, HealthState};
+
+pub mod failover;
+pub use failover::{FailoverMonitorService, FailoverReport};
+
+pub mod discovery;
+pub use discovery::{DiscoveryStatus, NodeDiscoveryService};
+
 /// Service is a core component of the WagLayla NG application responsible for
 /// running application services and communication between these services.
 #[async_trait]
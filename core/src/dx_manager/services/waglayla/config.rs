@@ -1,7 +1,8 @@
 use crate::app::{GIT_DESCRIBE, VERSION};
 use crate::imports::*;
-use crate::utils::Arglist;
+use crate::utils::{join_args, Arglist};
 use waglayla_core::waglaylad_env;
+use waglayla_utils::networking::NetAddress;
 #[cfg(not(target_arch = "wasm32"))]
 pub use waglaylad_lib::args::Args;
 
@@ -20,32 +21,248 @@ fn user_agent() -> String {
   )
 }
 
+/// Flags that are driven by the node settings and must not be overridden by custom arguments
+const RESERVED_ARGS: &[&str] = &[
+  "-b",
+  "--appdir",
+  "--rpclisten",
+  "--rpclisten-borsh",
+  "--rpclisten-json",
+  "--nogrpc",
+  "--disable-upnp",
+  "--ram-scale",
+  "--outpeers",
+  "--maxinpeers",
+  "--rpcmaxclients",
+  "--archival",
+  // the network is selected through `Network::daemon_args()`
+  "--testnet",
+  "--netsuffix",
+  "--devnet",
+  "--simnet",
+];
+
+/// Name of the flag in `arg`: `--flag` for long flags, `-f` for short flags,
+/// whose value may be attached as in `-fvalue`, and `None` for values.
+fn flag_name(arg: &str) -> Option<&str> {
+  if arg.starts_with("--") {
+    arg.split('=').next()
+  } else if arg.starts_with('-') {
+    arg.char_indices().nth(1).map(|(index, c)| &arg[..index + c.len_utf8()])
+  } else {
+    None
+  }
+}
+
+/// Folds flags followed by a separate value into `--flag=value` or `-f=value`,
+/// so the pairs survive the sorting done by [`Arglist`].
+fn fold_args(args: Vec<String>) -> Vec<String> {
+  let mut list: Vec<String> = Vec::new();
+  for arg in args {
+    match list.last_mut() {
+      // a bare `--flag` or `-f` without an attached value takes the next value
+      Some(last) if flag_name(&arg).is_none() && flag_name(last).map(|flag| flag == last.as_str()).unwrap_or(false) => {
+        last.push('=');
+        last.push_str(&arg);
+      }
+      _ => list.push(arg),
+    }
+  }
+  list
+}
+
+/// Splits the custom argument string, rejecting flags reserved by the node settings.
+pub fn parse_custom_args(args: &str) -> Result<Vec<String>> {
+  let list = fold_args(split_args(args)?);
+  if let Some(name) = list.iter().filter_map(|arg| flag_name(arg)).find(|name| RESERVED_ARGS.contains(name)) {
+    return Err(Error::ReservedNodeArg(name.to_string()));
+  }
+  Ok(list)
+}
+
+/// Moves flags that used to be passed as custom arguments into the typed node
+/// settings that now drive them, so configs saved before these flags were reserved
+/// keep starting the node. Returns the migrated flags.
+pub fn migrate_custom_args(node_settings: &mut NodeSettings) -> Vec<String> {
+  let Ok(args) = split_args(&node_settings.waglaylad_daemon_args) else {
+    return Vec::new();
+  };
+
+  let mut migrated = Vec::new();
+  let mut remaining = Vec::new();
+  for arg in fold_args(args) {
+    let (name, value) = match arg.split_once('=') {
+      Some((name, value)) => (name, Some(value)),
+      None => (arg.as_str(), None),
+    };
+
+    // values the settings cannot hold are left in place and reported when the node starts
+    let applied = match (name, value) {
+      ("--ram-scale", Some(value)) => value.parse::<f64>().ok().filter(|value| value.is_finite()).map(|value| {
+        node_settings.ram_scale = value.clamp(0.1, 10.0);
+      }),
+      ("--outpeers", Some(value)) => value.parse::<u16>().ok().map(|value| {
+        node_settings.outbound_target = value.clamp(1, 64);
+      }),
+      ("--maxinpeers", Some(value)) => value.parse::<u16>().ok().map(|value| {
+        node_settings.inbound_limit = value.min(1024);
+      }),
+      ("--rpcmaxclients", Some(value)) => value.parse::<u16>().ok().map(|value| {
+        node_settings.rpc_max_clients = value.clamp(1, 1024);
+      }),
+      ("--archival", None) => {
+        node_settings.archival = true;
+        Some(())
+      }
+      ("--archival", Some(value)) => value.parse::<bool>().ok().map(|value| {
+        node_settings.archival = value;
+      }),
+      _ => None,
+    };
+
+    if applied.is_some() {
+      migrated.push(arg);
+    } else {
+      remaining.push(arg);
+    }
+  }
+
+  if !migrated.is_empty() {
+    node_settings.waglaylad_daemon_args = join_args(&remaining);
+  }
+  migrated
+}
+
+// keep only the first line of the parser error (the rest is usage help)
+fn parser_error(err: impl std::fmt::Display) -> Error {
+  let message = err.to_string();
+  let message = message.lines().next().unwrap_or_default().trim_start_matches("error: ");
+  Error::InvalidNodeArgs(message.to_string())
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
+  network: Network,
   enable_upnp: bool,
   enable_wrpc_borsh: bool,
-  #[allow(dead_code)]
+  wrpc_borsh_network_interface: NetworkInterfaceConfig,
   enable_wrpc_json: bool,
+  wrpc_json_network_interface: NetworkInterfaceConfig,
   enable_grpc: bool,
   grpc_network_interface: NetworkInterfaceConfig,
+  // in-process nodes are reached through RpcCoreService and need no local wRPC listener
+  wrpc_loopback: bool,
   waglaylad_daemon_args_enable: bool,
   waglaylad_daemon_args: String,
   waglaylad_daemon_storage_folder_enable: bool,
   waglaylad_daemon_storage_folder: String,
+  shutdown_timeout: Duration,
+  ram_scale: f64,
+  outbound_target: u16,
+  inbound_limit: u16,
+  rpc_max_clients: u16,
+  archival: bool,
+}
+
+impl Config {
+  /// Time the node is given to exit after SIGTERM before it is killed
+  pub fn shutdown_timeout(&self) -> Duration {
+    self.shutdown_timeout
+  }
+
+  /// Address of the wRPC Borsh listener, falling back to the loopback
+  /// listener the wallet needs when the public one is disabled
+  fn wrpc_borsh_listener(&self) -> Option<NetAddress> {
+    let borsh_port = NetworkId::from(self.network).default_borsh_rpc_port();
+    if self.enable_wrpc_borsh {
+      Some(ContextualNetAddress::from(self.wrpc_borsh_network_interface.clone()).normalize(borsh_port))
+    } else if self.wrpc_loopback {
+      Some(ContextualNetAddress::loopback().normalize(borsh_port))
+    } else {
+      None
+    }
+  }
+
+  fn wrpc_json_listener(&self) -> Option<NetAddress> {
+    let json_port = NetworkId::from(self.network).default_json_rpc_port();
+    self.enable_wrpc_json
+      .then(|| ContextualNetAddress::from(self.wrpc_json_network_interface.clone()).normalize(json_port))
+  }
+
+  fn grpc_listener(&self) -> Option<NetAddress> {
+    let grpc_port = NetworkId::from(self.network).default_rpc_port();
+    self.enable_grpc
+      .then(|| ContextualNetAddress::from(self.grpc_network_interface.clone()).normalize(grpc_port))
+  }
+
+  /// Address the wallet uses to reach the node over wRPC Borsh
+  pub fn wrpc_client_url(&self) -> String {
+    match self.wrpc_borsh_listener() {
+      Some(address) if !address.ip.is_unspecified() => address.to_string(),
+      Some(address) => format!("127.0.0.1:{}", address.port),
+      None => "127.0.0.1".to_string(),
+    }
+  }
+
+  /// Enabled RPC listeners of the node
+  fn rpc_listeners(&self) -> Vec<(&'static str, NetAddress)> {
+    let listeners = [
+      ("wRPC Borsh", self.wrpc_borsh_listener()),
+      ("wRPC JSON", self.wrpc_json_listener()),
+      ("gRPC", self.grpc_listener()),
+    ];
+    listeners.into_iter().filter_map(|(name, address)| address.map(|address| (name, address))).collect()
+  }
+
+  /// Addresses bound by the node, including the P2P listener on all interfaces
+  pub fn listeners(&self) -> Vec<(&'static str, std::net::SocketAddr)> {
+    let p2p_port = NetworkId::from(self.network).default_p2p_port();
+    self.rpc_listeners()
+      .into_iter()
+      .map(|(name, address)| (name, std::net::SocketAddr::new(*address.ip, address.port)))
+      .chain(std::iter::once(("P2P", std::net::SocketAddr::from((std::net::Ipv4Addr::UNSPECIFIED, p2p_port)))))
+      .collect()
+  }
+
+  /// Returns the first pair of enabled RPC listeners that would bind the same port
+  pub fn listener_conflict(&self) -> Option<(&'static str, &'static str)> {
+    let listeners = self.rpc_listeners();
+
+    for (index, &(first, a)) in listeners.iter().enumerate() {
+      for &(second, b) in listeners.iter().skip(index + 1) {
+        let overlap = a.ip == b.ip || a.ip.is_unspecified() || b.ip.is_unspecified();
+        if a.port == b.port && overlap {
+          return Some((first, second));
+        }
+      }
+    }
+    None
+  }
 }
 
 impl From<NodeSettings> for Config {
   fn from(node_settings: NodeSettings) -> Self {
     Self {
+      network: node_settings.network,
       enable_upnp: node_settings.enable_upnp,
-      enable_wrpc_borsh: node_settings.enable_wrpc_borsh,
+      // the in-process node opens no wRPC Borsh port, whatever the setting says
+      enable_wrpc_borsh: node_settings.enable_wrpc_borsh && node_settings.node_kind != WagLayladNodeKind::IntegratedInProc,
+      wrpc_borsh_network_interface: node_settings.wrpc_borsh_network_interface,
       enable_wrpc_json: node_settings.enable_wrpc_json,
+      wrpc_json_network_interface: node_settings.wrpc_json_network_interface,
       enable_grpc: node_settings.enable_grpc,
       grpc_network_interface: node_settings.grpc_network_interface,
+      wrpc_loopback: node_settings.node_kind != WagLayladNodeKind::IntegratedInProc,
       waglaylad_daemon_args_enable: node_settings.waglaylad_daemon_args_enable,
       waglaylad_daemon_args: node_settings.waglaylad_daemon_args,
       waglaylad_daemon_storage_folder_enable: node_settings.waglaylad_daemon_storage_folder_enable,
       waglaylad_daemon_storage_folder: node_settings.waglaylad_daemon_storage_folder,
+      shutdown_timeout: Duration::from_secs(node_settings.waglaylad_daemon_shutdown_timeout),
+      ram_scale: node_settings.ram_scale,
+      outbound_target: node_settings.outbound_target,
+      inbound_limit: node_settings.inbound_limit,
+      rpc_max_clients: node_settings.rpc_max_clients,
+      archival: node_settings.archival,
     }
   }
 }
@@ -55,35 +272,30 @@ cfg_if! {
     impl TryFrom<Config> for Args {
       type Error = Error;
       fn try_from(config: Config) -> Result<Self> {
-        let mut args = Args::default();
-
-        args.perf_metrics = true;
-        args.perf_metrics_interval_sec = 1;
-        args.yes = true;
-        args.utxoindex = true;
-        args.disable_upnp = !config.enable_upnp;
-
-        if config.enable_grpc {
-          args.rpclisten = Some(config.grpc_network_interface.into());
-        }
-
-        args.user_agent_comments = vec![user_agent_comment()];
-
-        // TODO - parse custom args and overlap on top of the defaults
-
-        Ok(args)
+        // run the exact argument list handed to the daemon through the node parser,
+        // overlaying the custom arguments on top of the defaults
+        let args: Vec<String> = config.try_into()?;
+        Args::parse(std::iter::once("waglaylad".to_string()).chain(args)).map_err(parser_error)
       }
     }
 
-    impl From<Config> for Vec<String> {
-      fn from(config: Config) -> Self {
+    impl TryFrom<Config> for Vec<String> {
+      type Error = Error;
+      fn try_from(config: Config) -> Result<Self> {
         let mut args = Arglist::default();
 
         args.push("--perf-metrics");
         args.push("--perf-metrics-interval-sec=1");
         args.push("--yes");
         args.push("--utxoindex");
-        args.push(format!("--ram-scale={:1.2}", 1.0));
+        args.push(format!("--ram-scale={:1.2}", config.ram_scale));
+        args.push(format!("--outpeers={}", config.outbound_target));
+        args.push(format!("--maxinpeers={}", config.inbound_limit));
+        args.push(format!("--rpcmaxclients={}", config.rpc_max_clients));
+
+        if config.archival {
+          args.push("--archival");
+        }
 
         if !config.enable_upnp {
           args.push("--disable-upnp");
@@ -95,36 +307,106 @@ cfg_if! {
           args.push("--nogrpc");
         }
 
-        if config.enable_wrpc_borsh {
-          args.push("--rpclisten-borsh=0.0.0.0:13110");
-        } else {
-          args.push("--rpclisten-borsh=127.0.0.1:13110");
+        config.network.daemon_args().into_iter().for_each(|arg| args.push(arg));
+
+        if let Some(address) = config.wrpc_borsh_listener() {
+          args.push(format!("--rpclisten-borsh={address}"));
+        }
+
+        if let Some(address) = config.wrpc_json_listener() {
+          args.push(format!("--rpclisten-json={address}"));
         }
 
         args.push(format!("--uacomment={}", user_agent_comment()));
 
-        if config.waglaylad_daemon_storage_folder_enable && !config.waglaylad_daemon_storage_folder.is_empty() && !(config.waglaylad_daemon_args_enable && config.waglaylad_daemon_args.contains("--appdir")) {
+        if config.waglaylad_daemon_storage_folder_enable && !config.waglaylad_daemon_storage_folder.is_empty() {
           args.push(format!("--appdir={}", config.waglaylad_daemon_storage_folder));
         }
 
+        // reserved or malformed custom arguments refuse the start instead of being dropped
         if config.waglaylad_daemon_args_enable {
-          config.waglaylad_daemon_args.trim().split(' ').filter(|arg|!arg.trim().is_empty()).for_each(|arg| {
-            args.push(arg);
-          });
+          parse_custom_args(&config.waglaylad_daemon_args)?.into_iter().for_each(|arg| args.push(arg));
         }
 
-        args.into()
+        Ok(args.into())
       }
     }
+  }
+}
 
-    impl IntoIterator for Config {
-      type Item = String;
-      type IntoIter = std::vec::IntoIter<Self::Item>;
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-      fn into_iter(self) -> Self::IntoIter {
-        let args: Vec<String> = self.into();
-        args.into_iter()
-      }
+  #[test]
+  fn split_args_handles_quotes() {
+    assert_eq!(split_args("  --a=1\t-b  ").unwrap(), ["--a=1", "-b"]);
+    assert_eq!(split_args("--uacomment \"a b\" --x='c \"d\"'").unwrap(), ["--uacomment", "a b", "--x=c \"d\""]);
+    assert_eq!(split_args(r#""a\"b\\c\d" '' """#).unwrap(), ["a\"b\\c\\d", "", ""]);
+    assert!(split_args("--a 'b").is_err());
+    assert!(split_args("--a \"b\\\"").is_err());
+    assert!(split_args("").unwrap().is_empty());
+  }
+
+  #[test]
+  fn join_args_round_trips() {
+    let args = ["--a=1", "a b", "", "c\"d\\e", "'f'"].map(String::from);
+    assert_eq!(split_args(&join_args(&args)).unwrap(), args);
+  }
+
+  #[test]
+  fn flag_name_reads_long_and_short_flags() {
+    assert_eq!(flag_name("--appdir=/tmp/a=b"), Some("--appdir"));
+    assert_eq!(flag_name("--archival"), Some("--archival"));
+    assert_eq!(flag_name("-b"), Some("-b"));
+    // short flags may carry their value attached
+    assert_eq!(flag_name("-b/tmp/node"), Some("-b"));
+    assert_eq!(flag_name("-bar"), Some("-b"));
+    assert_eq!(flag_name("-é1"), Some("-é"));
+    assert_eq!(flag_name("-"), None);
+    assert_eq!(flag_name("value"), None);
+  }
+
+  #[test]
+  fn parse_custom_args_folds_values() {
+    assert_eq!(
+      parse_custom_args("--loglevel debug -x 1 --connect=a:1 --nodnsseed --addpeer \"b c\"").unwrap(),
+      ["--loglevel=debug", "-x=1", "--connect=a:1", "--nodnsseed", "--addpeer=b c"]
+    );
+    // a flag with an attached value does not take the next one
+    assert_eq!(parse_custom_args("--a=1 2 -x3 4").unwrap(), ["--a=1", "2", "-x3", "4"]);
+    assert_eq!(parse_custom_args("--a -c").unwrap(), ["--a", "-c"]);
+  }
+
+  #[test]
+  fn parse_custom_args_rejects_reserved_flags() {
+    for args in ["-b /tmp", "-b/tmp", "--appdir=/tmp", "--loglevel info --testnet", "--rpclisten-json :1"] {
+      assert!(matches!(parse_custom_args(args), Err(Error::ReservedNodeArg(_))), "{args}");
     }
+    assert!(matches!(parse_custom_args("--a \"b"), Err(Error::InvalidNodeArgs(_))));
+  }
+
+  #[test]
+  fn migrate_custom_args_moves_typed_flags() {
+    let mut node_settings = NodeSettings {
+      waglaylad_daemon_args: "--outpeers 16 --ram-scale=0.5 --archival --loglevel=debug --uacomment \"a b\"".to_string(),
+      ..Default::default()
+    };
+    let migrated = migrate_custom_args(&mut node_settings);
+    assert_eq!(migrated, ["--outpeers=16", "--ram-scale=0.5", "--archival"]);
+    assert_eq!(node_settings.outbound_target, 16);
+    assert_eq!(node_settings.ram_scale, 0.5);
+    assert!(node_settings.archival);
+    assert_eq!(node_settings.waglaylad_daemon_args, "--loglevel=debug \"--uacomment=a b\"");
+    assert!(parse_custom_args(&node_settings.waglaylad_daemon_args).is_ok());
+  }
+
+  #[test]
+  fn migrate_custom_args_keeps_unusable_values() {
+    let args = "--maxinpeers=lots --rpcmaxclients";
+    let mut node_settings = NodeSettings { waglaylad_daemon_args: args.to_string(), ..Default::default() };
+    assert!(migrate_custom_args(&mut node_settings).is_empty());
+    assert_eq!(node_settings.waglaylad_daemon_args, args);
+    assert_eq!(node_settings.inbound_limit, crate::settings::NODE_INBOUND_LIMIT);
   }
 }
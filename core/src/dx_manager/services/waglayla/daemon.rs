@@ -5,16 +5,20 @@ use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use workflow_core::prelude::DuplexChannel;
 
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 enum TerminationMethod {
-  #[default]
   Sigkill,
+  /// SIGTERM followed by SIGKILL if the node does not exit within the shutdown timeout
+  #[default]
   Sigterm,
 }
 
 struct Inner {
   path: Option<PathBuf>,
   is_running: Arc<AtomicBool>,
+  stop_requested: Arc<AtomicBool>,
   pid: Mutex<Option<u32>>,
   service_events: Channel<WagLayladServiceEvents>,
   task_ctl: DuplexChannel,
@@ -32,6 +36,7 @@ impl Daemon {
       inner: Arc::new(Inner {
         path,
         is_running: Arc::new(AtomicBool::new(false)),
+        stop_requested: Arc::new(AtomicBool::new(false)),
         pid: Mutex::new(None),
         service_events: (*service_events).clone(),
         task_ctl: DuplexChannel::oneshot(),
@@ -61,6 +66,8 @@ impl Daemon {
 #[async_trait]
 impl super::WagLaylad for Daemon {
   async fn start(self: Arc<Self>, config: Config) -> Result<()> {
+    let shutdown_timeout = config.shutdown_timeout();
+    let args: Vec<String> = config.try_into()?;
     let mut cmd = if let Some(path) = self.inner().path.clone() {
       Command::new(path)
     } else {
@@ -69,12 +76,14 @@ impl super::WagLaylad for Daemon {
     };
 
     let cmd = cmd
-      .args(config)
+      .args(args)
       .env("WALA_WAGDX_DAEMON", "1")
       .stdout(Stdio::piped());
 
     let is_running = self.inner().is_running.clone();
     is_running.store(true, Ordering::SeqCst);
+    let stop_requested = self.inner().stop_requested.clone();
+    stop_requested.store(false, Ordering::SeqCst);
     let mut child = cmd.spawn().map_err(Error::NodeStartupError)?;
     let stdout = child.stdout.take().ok_or(Error::NodeStdoutHandleError)?;
     *self.inner.pid.lock().unwrap() = child.id();
@@ -94,15 +103,27 @@ impl super::WagLaylad for Daemon {
     }
 
     tokio::spawn(async move {
+      let mut kill_deadline: Option<tokio::time::Instant> = None;
+
       loop {
+        let deadline = kill_deadline;
+        let kill_timer = async move {
+          match deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline).await,
+            None => futures::future::pending::<()>().await,
+          }
+        };
+
         select! {
           _ = task_ctl.request.recv().fuse() => {
             if this.inner.termination_method == TerminationMethod::Sigterm && is_unix {
-              let pid = this.inner.pid.lock().unwrap();
-              if let Some(_pid) = *pid {
+              let pid = *this.inner.pid.lock().unwrap();
+              if let Some(_pid) = pid {
                 #[cfg(unix)]
                 this.sigterm(_pid);
               }
+              // keep relaying stdout while the node flushes its database
+              kill_deadline = Some(tokio::time::Instant::now() + shutdown_timeout);
             } else {
               if let Err(err) = child.kill().await {
                 println!("Failed to kill waglaylad: {:?}", err);
@@ -112,16 +133,31 @@ impl super::WagLaylad for Daemon {
               }
             }
           }
+          _ = kill_timer.fuse() => {
+            println!("waglaylad did not exit within {:?}, sending SIGKILL", shutdown_timeout);
+            kill_deadline = None;
+            if let Err(err) = child.kill().await {
+              println!("Failed to kill waglaylad: {:?}", err);
+            }
+          }
           status = child.wait().fuse() => {
-            match status {
+            let status = match status {
               Ok(status) => {
                 println!("waglaylad shutdown: {:?}", status);
+                status.to_string()
               }
               Err(err) => {
                 println!("waglaylad shutdown error: {:?}", err);
+                err.to_string()
               }
-            }
+            };
             is_running.store(false,Ordering::SeqCst);
+
+            // an exit that was not requested via `stop()` is reported
+            // to the service so that the supervisor can restart the node
+            if !stop_requested.load(Ordering::SeqCst) {
+              stdout_relay_sender.try_send(WagLayladServiceEvents::DaemonExit { status }).ok();
+            }
             break;
           }
 
@@ -141,9 +177,38 @@ impl super::WagLaylad for Daemon {
   }
 
   async fn stop(self: Arc<Self>) -> Result<()> {
+    self.inner.stop_requested.store(true, Ordering::SeqCst);
     if self.is_running() {
       self.inner.task_ctl.signal(()).await?;
     }
     Ok(())
   }
 }
+
+/// Runs `<binary> --version` and returns the reported version number. A binary
+/// that does not answer within [`VERSION_PROBE_TIMEOUT`] is killed.
+pub async fn version(path: &Path) -> Result<String> {
+  if !path.is_file() {
+    return Err(Error::MissingExternalWagLayladBinary);
+  }
+
+  let probe = Command::new(path)
+    .arg("--version")
+    .stdin(Stdio::null())
+    .kill_on_drop(true)
+    .output();
+
+  let output = tokio::time::timeout(VERSION_PROBE_TIMEOUT, probe)
+    .await
+    .map_err(|_| Error::Custom(format!("{} did not report its version within {:?}", path.display(), VERSION_PROBE_TIMEOUT)))?
+    .map_err(Error::NodeStartupError)?;
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  // clap reports the version as `<name> <version>`
+  stdout
+    .split_whitespace()
+    .last()
+    .map(|version| version.trim_start_matches('v').to_string())
+    .filter(|_| output.status.success())
+    .ok_or_else(|| Error::Custom(format!("Unable to determine the version of {}", path.display())))
+}
@@ -0,0 +1,71 @@
+use crate::imports::*;
+use crate::dx_manager::services::waglayla::Config;
+use waglayla_core::core::Core as NodeCore;
+use waglayla_rpc_service::service::RpcCoreService;
+use waglayla_utils::fd_budget;
+use waglaylad_lib::args::Args;
+use waglaylad_lib::daemon::create_core;
+
+#[derive(Default)]
+struct Inner {
+  thread: Option<std::thread::JoinHandle<()>>,
+  core: Option<Arc<NodeCore>>,
+  rpc_core_service: Option<Arc<RpcCoreService>>,
+}
+
+/// Runs the node core inside the application process. The wallet is bound
+/// directly to the node's [`RpcCoreService`], so no wRPC listener is needed.
+#[derive(Default)]
+pub struct InProc {
+  inner: Mutex<Inner>,
+}
+
+impl InProc {
+  pub fn rpc_core_service(&self) -> Option<Arc<RpcCoreService>> {
+    self.inner.lock().unwrap().rpc_core_service.clone()
+  }
+}
+
+#[async_trait]
+impl super::WagLaylad for InProc {
+  async fn start(self: Arc<Self>, config: Config) -> Result<()> {
+    let args = Args::try_from(config)?;
+    let fd_total_budget = fd_budget::limit() - args.rpc_max_clients as i32 - args.inbound_limit as i32 - args.outbound_target as i32;
+    let (core, rpc_core_service) = create_core(args, fd_total_budget);
+
+    let node_core = core.clone();
+    let thread = std::thread::Builder::new()
+      .name("waglaylad".to_string())
+      .spawn(move || node_core.run())
+      .map_err(Error::NodeStartupError)?;
+
+    let mut inner = self.inner.lock().unwrap();
+    inner.thread = Some(thread);
+    inner.core = Some(core);
+    inner.rpc_core_service = Some(rpc_core_service);
+
+    Ok(())
+  }
+
+  async fn stop(self: Arc<Self>) -> Result<()> {
+    let (core, thread) = {
+      let mut inner = self.inner.lock().unwrap();
+      inner.rpc_core_service.take();
+      (inner.core.take(), inner.thread.take())
+    };
+
+    if let Some(core) = core {
+      core.shutdown();
+    }
+
+    // core.run() returns once every node service has been joined
+    if let Some(thread) = thread {
+      tokio::task::spawn_blocking(move || thread.join())
+        .await
+        .map_err(|err| Error::Custom(err.to_string()))?
+        .map_err(|_| Error::Custom("waglaylad core thread panicked".to_string()))?;
+    }
+
+    Ok(())
+  }
+}
@@ -10,9 +10,11 @@ use waglayla_wallet_core::rpc::{
 use waglayla_wrpc_client::Resolver;
 use workflow_core::runtime;
 
-use std::io::Write;
 use std::sync::Mutex;
 
+pub mod verify;
+use verify::Verification;
+
 const ENABLE_PREEMPTIVE_DISCONNECT: bool = true;
 
 cfg_if! {
@@ -23,15 +25,48 @@ cfg_if! {
     const LOG_BUFFER_LINES: usize = 4096;
     const LOG_BUFFER_MARGIN: usize = 128;
 
+    const DAEMON_RESTART_MAX_BACKOFF: u64 = 60;
+    const DAEMON_STABLE_UPTIME: Duration = Duration::from_secs(5 * 60);
+
     pub mod config;
     pub use config::Config;
     pub mod daemon;
+    pub mod inproc;
+    pub mod socks;
+    pub mod tunnel;
 
     #[async_trait]
     pub trait WagLaylad {
       async fn start(self : Arc<Self>, config : Config) -> Result<()>;
       async fn stop(self : Arc<Self>) -> Result<()>;
     }
+
+    /// Restart policy applied when the integrated node exits without being asked to
+    #[derive(Debug, Clone, Copy)]
+    pub struct RestartPolicy {
+      pub enabled: bool,
+      pub max_attempts: u32,
+    }
+
+    impl From<&NodeSettings> for RestartPolicy {
+      fn from(node_settings: &NodeSettings) -> Self {
+        Self {
+          enabled: node_settings.waglaylad_daemon_restart_enable,
+          max_attempts: node_settings.waglaylad_daemon_restart_limit,
+        }
+      }
+    }
+
+    #[derive(Default)]
+    struct Supervisor {
+      // node binary to launch; `None` re-executes the current executable
+      path: Option<PathBuf>,
+      config: Option<Config>,
+      crashes: u32,
+      started: Option<Instant>,
+      // bumped on every user-initiated start/stop, invalidating pending restarts
+      generation: u64,
+    }
   }
 }
 
@@ -42,8 +77,26 @@ cfg_if! {
     pub enum WagLayladServiceEvents {
       #[cfg(not(target_arch = "wasm32"))]
       StartInternalAsDaemon { config: Config, network: Network },
+      #[cfg(not(target_arch = "wasm32"))]
+      StartInternalInProc { config: Config, network: Network },
+      #[cfg(not(target_arch = "wasm32"))]
+      StartExternalAsDaemon { path: PathBuf, config: Config, network: Network },
+      #[cfg(not(target_arch = "wasm32"))]
+      MigrateStorage { migration: crate::storage::StorageMigration },
       StartRemoteConnection { rpc_config : RpcConfig, network : Network },
+      RemoteNodeVerified {
+        rpc_config : RpcConfig,
+        url : String,
+        network : Network,
+        generation : u64,
+        verification : std::result::Result<Verification, String>,
+      },
+      DiscoverPublicNode { network : Network, proxy : Option<ProxySettings> },
       Stdout { line : String },
+      DaemonExit { status : String },
+      RestartDaemon { generation : u64 },
+      ReconnectRpc,
+      Stop,
       Disable { network : Network },
       Exit,
     }
@@ -51,6 +104,15 @@ cfg_if! {
     #[derive(Debug)]
     pub enum WagLayladServiceEvents {
       StartRemoteConnection { rpc_config : RpcConfig, network : Network },
+      RemoteNodeVerified {
+        rpc_config : RpcConfig,
+        url : String,
+        network : Network,
+        generation : u64,
+        verification : std::result::Result<Verification, String>,
+      },
+      ReconnectRpc,
+      Stop,
       Disable { network : Network },
       Exit,
     }
@@ -66,10 +128,21 @@ pub struct WagLaylaService {
   pub services_start_instant: Mutex<Option<Instant>>,
   #[cfg(not(target_arch = "wasm32"))]
   pub waglaylad: Mutex<Option<Arc<dyn WagLaylad + Send + Sync + 'static>>>,
-  pub log_file: Mutex<std::fs::File>,
+  #[cfg(not(target_arch = "wasm32"))]
+  supervisor: Mutex<Supervisor>,
+  #[cfg(not(target_arch = "wasm32"))]
+  restart_policy: Mutex<RestartPolicy>,
+  #[cfg(not(target_arch = "wasm32"))]
+  tunnel: Mutex<Option<tunnel::Tunnel>>,
+  // cancel flag and task of a running data folder migration
+  #[cfg(not(target_arch = "wasm32"))]
+  migration: Mutex<Option<(Arc<AtomicBool>, tokio::task::JoinHandle<()>)>>,
+  pub log_file: Mutex<crate::platform::log_rotation::RotatingLogFile>,
   pub daemon_sender: Sender<DaemonMessage>,
   pub connect_on_startup: Option<NodeSettings>,
   pub url: Mutex<Option<String>>,
+  // bumped whenever the services stop, so that a pending remote node check is discarded
+  remote_generation: AtomicU64,
 }
 
 impl WagLaylaService {
@@ -80,7 +153,7 @@ impl WagLaylaService {
     wallet: Option<Arc<dyn WalletApi>>,
   ) -> Self {
 
-    let log_file = crate::platform::paths::open_log_file("rusty-waglayla-service").expect("Failed to Create Log File:");
+    let log_file = crate::platform::paths::open_log_file("rusty-waglayla-service", settings.logs.clone()).expect("Failed to Create Log File:");
 
     let storage = CoreWallet::local_store().unwrap_or_else(|e| {
       panic!("Failed to open local store: {}", e);
@@ -88,7 +161,7 @@ impl WagLaylaService {
 
     let wallet = wallet.unwrap_or_else(|| {
       Arc::new(
-        CoreWallet::try_with_rpc(None, storage, Some(Network::Mainnet.into()))
+        CoreWallet::try_with_rpc(None, storage, Some(settings.node.network.into()))
           .unwrap_or_else(|e| {
             panic!("Failed to create wallet instance: {}", e);
           }),
@@ -103,15 +176,24 @@ impl WagLaylaService {
       application_events,
       service_events: Channel::unbounded(),
       task_ctl: Channel::oneshot(),
-      network: Mutex::new(Network::Mainnet),
+      network: Mutex::new(settings.node.network),
       wallet,
       services_start_instant: Mutex::new(None),
       #[cfg(not(target_arch = "wasm32"))]
       waglaylad: Mutex::new(None),
+      #[cfg(not(target_arch = "wasm32"))]
+      supervisor: Mutex::new(Supervisor::default()),
+      #[cfg(not(target_arch = "wasm32"))]
+      restart_policy: Mutex::new(RestartPolicy::from(&settings.node)),
+      #[cfg(not(target_arch = "wasm32"))]
+      tunnel: Mutex::new(None),
+      #[cfg(not(target_arch = "wasm32"))]
+      migration: Mutex::new(None),
       log_file: Mutex::new(log_file),
       daemon_sender,
       connect_on_startup: settings.initialized.then(|| settings.node.clone()),
       url: Mutex::new(None),
+      remote_generation: AtomicU64::new(0),
     }
   }
 
@@ -120,14 +202,213 @@ impl WagLaylaService {
     self.waglaylad.lock().unwrap().replace(waglaylad);
   }
 
+  /// Stops the node and moves its data folder; the application restarts the node once the migration event arrives.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn migrate_storage(&self, migration: crate::storage::StorageMigration) {
+    self.service_events
+      .sender
+      .try_send(WagLayladServiceEvents::MigrateStorage { migration })
+      .unwrap_or_else(|err| {
+        log_error!("WagLayladService error: {}", err);
+      });
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  fn is_migrating(&self) -> bool {
+    self.migration.lock().unwrap().as_ref().map(|(_, task)| !task.is_finished()).unwrap_or(false)
+  }
+
+  /// Runs a data folder migration off the service loop and reports the outcome as an application event
+  #[cfg(not(target_arch = "wasm32"))]
+  async fn run_migration(self: Arc<Self>, mut migration: crate::storage::StorageMigration, cancel: Arc<AtomicBool>) {
+    let sender = self.application_events.sender.clone();
+    let progress = migration.clone();
+    let result = tokio::task::spawn_blocking(move || {
+      crate::storage::migrate_folder(&progress.source, &progress.target, progress.remove_source, &cancel, |stage, copied, total| {
+        let migration = crate::storage::StorageMigration { stage, copied, total, ..progress.clone() };
+        sender.try_send(crate::events::Events::StorageMigration(migration)).ok();
+      })
+    })
+    .await
+    .map_err(|err| Error::StorageMigration(err.to_string()))
+    .and_then(|result| result);
+
+    match result {
+      Ok(leftover) => {
+        migration.stage = crate::storage::MigrationStage::Done;
+        migration.copied = migration.total;
+        migration.leftover = leftover;
+      }
+      Err(err) => {
+        log_error!("data folder migration failed: {err}");
+        migration.stage = crate::storage::MigrationStage::Failed(err.to_string());
+      }
+    }
+    self.notify(crate::events::Events::StorageMigration(migration)).ok();
+  }
+
+  /// Connects to a public node found by the [`NodeDiscoveryService`](crate::dx_manager::services::NodeDiscoveryService)
+  pub fn connect_public_node(&self, url: String, network: Network, proxy: Option<ProxySettings>) {
+    let rpc_config = RpcConfig::Wrpc {
+      url: Some(url),
+      // public nodes are discovered on the Borsh port
+      encoding: WrpcEncoding::Borsh,
+      resolver_urls: None,
+      tls: TlsTrust::default(),
+      proxy,
+    };
+    self.service_events
+      .sender
+      .try_send(WagLayladServiceEvents::StartRemoteConnection { rpc_config, network })
+      .unwrap_or_else(|err| {
+        log_error!("WagLayladService error: {}", err);
+      });
+  }
+
+  /// Stops the node and drops the RPC connection while keeping the node settings and the open wallet intact
+  pub fn stop_node(&self) {
+    manager().node_discovery().cancel();
+    self.service_events
+      .sender
+      .try_send(WagLayladServiceEvents::Stop)
+      .unwrap_or_else(|err| {
+        log_error!("WagLayladService error: {}", err);
+      });
+  }
+
+  /// Drops and re-establishes the RPC connection to the current node
+  pub fn reconnect_rpc(&self) {
+    self.service_events
+      .sender
+      .try_send(WagLayladServiceEvents::ReconnectRpc)
+      .unwrap_or_else(|err| {
+        log_error!("WagLayladService error: {}", err);
+      });
+  }
+
+  pub fn update_log_settings(&self, log_settings: &LogSettings) {
+    self.log_file.lock().unwrap().set_policy(log_settings.clone());
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn update_restart_policy(&self, node_settings: &NodeSettings) {
+    *self.restart_policy.lock().unwrap() = RestartPolicy::from(node_settings);
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  async fn start_daemon(&self, path: Option<PathBuf>, config: Config) -> Result<()> {
+    let waglaylad = Arc::new(daemon::Daemon::new(path, &self.service_events));
+    self.retain(waglaylad.clone());
+    self.supervisor.lock().unwrap().started = Some(Instant::now());
+    waglaylad.start(config).await
+  }
+
+  /// Launches the node as a supervised child process and connects the wallet to it over the wRPC loopback.
+  #[cfg(not(target_arch = "wasm32"))]
+  async fn start_daemon_services(self: &Arc<Self>, path: Option<PathBuf>, config: Config, network: Network) -> Result<()> {
+    {
+      let mut supervisor = self.supervisor.lock().unwrap();
+      supervisor.path = path.clone();
+      supervisor.config = Some(config.clone());
+      supervisor.crashes = 0;
+    }
+    let url = config.wrpc_client_url();
+    self.start_daemon(path, config).await?;
+    self.notify(crate::events::Events::DaemonStarted { crashes: 0 })?;
+
+    // the daemon configuration always provides a Borsh listener for the wallet
+    let rpc = Self::create_rpc_client(Some(url.clone()), None, WrpcEncoding::Borsh, network)
+      .expect("WagLaylad Service - unable to create wRPC client");
+    *self.url.lock().unwrap() = Some(url);
+    self.start_all_services(Some(rpc), network).await?;
+    self.connect_rpc_client().await?;
+
+    self.update_storage();
+    Ok(())
+  }
+
+  /// Runs the node core in this process and binds the wallet directly to its RPC service.
+  #[cfg(not(target_arch = "wasm32"))]
+  async fn start_inproc_services(self: &Arc<Self>, config: Config, network: Network) -> Result<()> {
+    let waglaylad = Arc::new(inproc::InProc::default());
+    self.retain(waglaylad.clone());
+    waglaylad.clone().start(config).await?;
+
+    let rpc_api: Arc<DynRpcApi> = waglaylad
+      .rpc_core_service()
+      .expect("WagLaylad Service - unable to obtain in-process rpc core service");
+    let rpc = Rpc::new(rpc_api, RpcCtl::new());
+    *self.url.lock().unwrap() = None;
+    self.start_all_services(Some(rpc), network).await?;
+    self.connect_rpc_client().await?;
+
+    self.update_storage();
+    Ok(())
+  }
+
+  /// Reports a node that could not be started; the event loop keeps running so that
+  /// the user can correct the settings and start the node again.
+  #[cfg(not(target_arch = "wasm32"))]
+  fn handle_start_error(&self, err: Error) -> Result<bool> {
+    log_error!("unable to start waglaylad: {err}");
+    // nothing was started, so there is nothing for the supervisor to restart
+    self.supervisor.lock().unwrap().config = None;
+    self.notify(crate::events::Events::DaemonStartError { error: err.to_string() })?;
+    Ok(false)
+  }
+
+  /// Registers an unrequested node exit and schedules a restart with
+  /// exponential backoff until the restart policy limit is reached.
+  #[cfg(not(target_arch = "wasm32"))]
+  fn handle_daemon_exit(&self, status: String) -> Result<()> {
+    let policy = *self.restart_policy.lock().unwrap();
+    let mut supervisor = self.supervisor.lock().unwrap();
+    if supervisor.config.is_none() {
+      return Ok(());
+    }
+
+    if supervisor.started.map(|started| started.elapsed() > DAEMON_STABLE_UPTIME).unwrap_or(false) {
+      supervisor.crashes = 0;
+    }
+    supervisor.crashes += 1;
+    supervisor.started = None;
+
+    let crashes = supervisor.crashes;
+    let restart_in = (policy.enabled && crashes <= policy.max_attempts)
+      .then(|| Duration::from_secs((1u64 << (crashes - 1).min(6)).min(DAEMON_RESTART_MAX_BACKOFF)));
+
+    log_warn!("waglaylad exited unexpectedly ({status}), crash count: {crashes}");
+
+    if let Some(delay) = restart_in {
+      let generation = supervisor.generation;
+      let sender = self.service_events.sender.clone();
+      tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        sender.try_send(WagLayladServiceEvents::RestartDaemon { generation }).ok();
+      });
+    } else {
+      supervisor.config = None;
+    }
+
+    self.notify(crate::events::Events::DaemonCrash { crashes, status, restart_in })
+  }
+
   async fn stop_daemon(&self) -> Result<()> {
     #[cfg(not(target_arch = "wasm32"))]
     {
+      {
+        let mut supervisor = self.supervisor.lock().unwrap();
+        supervisor.config = None;
+        supervisor.generation += 1;
+      }
+
       let waglaylad = self.waglaylad.lock().unwrap().take();
       if let Some(waglaylad) = waglaylad {
+        self.notify(crate::events::Events::DaemonStopping).ok();
         if let Err(err) = waglaylad.stop().await {
           log_error!("error shutting down waglaylad: {}", err);
         }
+        self.notify(crate::events::Events::DaemonStopped).ok();
       }
     }
     Ok(())
@@ -159,6 +440,7 @@ impl WagLaylaService {
 
   pub async fn stop_all_services(&self) -> Result<()> {
     self.services_start_instant.lock().unwrap().take();
+    self.remote_generation.fetch_add(1, Ordering::SeqCst);
 
     if let Some(wallet) = self.core_wallet() {
       if !wallet.has_rpc() {
@@ -195,6 +477,8 @@ impl WagLaylaService {
     }
 
     self.stop_daemon().await?;
+    #[cfg(not(target_arch = "wasm32"))]
+    self.tunnel.lock().unwrap().take();
     Ok(())
   }
 
@@ -244,6 +528,7 @@ impl WagLaylaService {
   }
 
   pub async fn apply_node_settings(&self, node_settings: &NodeSettings) -> Result<()> {
+    manager().node_discovery().cancel();
     match WagLayladServiceEvents::from_node_settings(node_settings, None) {
       Ok(event) => {
         self.service_events
@@ -261,6 +546,7 @@ impl WagLaylaService {
   }
 
   pub fn update_services(&self, node_settings: &NodeSettings, options: Option<RpcOptions>) {
+    manager().node_discovery().cancel();
     match WagLayladServiceEvents::from_node_settings(node_settings, options) {
       Ok(event) => {
         self.service_events
@@ -304,9 +590,26 @@ impl WagLaylaService {
   }
 
   async fn handle_event(self: &Arc<Self>, event: WagLayladServiceEvents) -> Result<bool> {
+    // the node is restarted by the application once the migration reports its outcome
+    #[cfg(not(target_arch = "wasm32"))]
+    if self.is_migrating() && matches!(
+      event,
+      WagLayladServiceEvents::StartInternalAsDaemon { .. }
+        | WagLayladServiceEvents::StartInternalInProc { .. }
+        | WagLayladServiceEvents::StartExternalAsDaemon { .. }
+        | WagLayladServiceEvents::MigrateStorage { .. }
+    ) {
+      log_warn!("ignoring node start while the data folder is being migrated");
+      return Ok(false);
+    }
+
     match event {
       #[cfg(not(target_arch = "wasm32"))]
       WagLayladServiceEvents::Stdout { line } => {
+        if let Some(event) = crate::node_log::NodeLogEvent::parse(&line) {
+          self.notify(crate::events::Events::NodeLog(event))?;
+        }
+
         let wallet = self.core_wallet().ok_or(Error::WalletIsNotLocal)?;
         if !wallet.utxo_processor().is_synced() {
           wallet
@@ -316,10 +619,7 @@ impl WagLaylaService {
             .await?;
         }
 
-        let log_message = format!("{}\n", line);
-
-        let mut file = self.log_file.lock().unwrap();
-        if let Err(e) = file.write_all(log_message.as_bytes()) {
+        if let Err(e) = self.log_file.lock().unwrap().write_line(&line) {
           log_error!("Failed to write to log file: {}", e);
         }
 
@@ -328,18 +628,73 @@ impl WagLaylaService {
       #[cfg(not(target_arch = "wasm32"))]
       WagLayladServiceEvents::StartInternalAsDaemon { config, network } => {
         self.stop_all_services().await?;
+        if let Err(err) = self.start_daemon_services(None, config, network).await {
+          return self.handle_start_error(err);
+        }
+      }
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladServiceEvents::StartExternalAsDaemon { path, config, network } => {
+        self.stop_all_services().await?;
 
-        let waglaylad = Arc::new(daemon::Daemon::new(None, &self.service_events));
-        self.retain(waglaylad.clone());
-        waglaylad.clone().start(config).await.unwrap();
+        let version = match daemon::version(&path).await {
+          Ok(version) => version,
+          Err(err) => {
+            log_error!("unable to start external waglaylad {}: {err}", path.display());
+            self.notify(crate::events::Events::DaemonStartError { error: err.to_string() })?;
+            return Ok(false);
+          }
+        };
 
-        let rpc = Self::create_rpc_client(Some("127.0.0.1".to_string()), None)
-          .expect("WagLaylad Service - unable to create wRPC client");
-        *self.url.lock().unwrap() = Some("127.0.0.1".to_string());
-        self.start_all_services(Some(rpc), network).await?;
-        self.connect_rpc_client().await?;
+        let compatible = verify::is_compatible_version(&version);
+        if !compatible {
+          log_warn!("external waglaylad version {version} may be incompatible with {}", waglayla_wallet_core::version());
+        }
+        self.notify(crate::events::Events::DaemonVersion { version, compatible })?;
+
+        // the binary may still fail to launch, e.g. if it was removed or lost its permissions after the probe
+        if let Err(err) = self.start_daemon_services(Some(path), config, network).await {
+          return self.handle_start_error(err);
+        }
+      }
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladServiceEvents::StartInternalInProc { config, network } => {
+        self.stop_all_services().await?;
+        // invalid custom arguments are only detected when the node arguments are built
+        if let Err(err) = self.start_inproc_services(config, network).await {
+          return self.handle_start_error(err);
+        }
+      }
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladServiceEvents::MigrateStorage { migration } => {
+        self.stop_all_services().await?;
+
+        // the copy can take hours, the service keeps handling events meanwhile
+        let cancel = Arc::new(AtomicBool::new(false));
+        let task = tokio::spawn(self.clone().run_migration(migration, cancel.clone()));
+        *self.migration.lock().unwrap() = Some((cancel, task));
+      }
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladServiceEvents::DaemonExit { status } => {
+        self.handle_daemon_exit(status)?;
+      }
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladServiceEvents::RestartDaemon { generation } => {
+        let (path, config, crashes) = {
+          let supervisor = self.supervisor.lock().unwrap();
+          if supervisor.generation != generation {
+            return Ok(false);
+          }
+          (supervisor.path.clone(), supervisor.config.clone(), supervisor.crashes)
+        };
 
-        self.update_storage();
+        if let Some(config) = config {
+          log_info!("restarting waglaylad (attempt {crashes})");
+          // the wRPC client uses a retry strategy and reconnects on its own
+          match self.start_daemon(path, config).await {
+            Ok(_) => self.notify(crate::events::Events::DaemonStarted { crashes })?,
+            Err(err) => self.handle_daemon_exit(err.to_string())?,
+          }
+        }
       }
       WagLayladServiceEvents::StartRemoteConnection {
         rpc_config,
@@ -353,12 +708,80 @@ impl WagLaylaService {
         } else {
           self.stop_all_services().await?;
 
-          let rpc = Self::create_rpc_client(rpc_config.url(), None)
-            .expect("WagLaylad Service - unable to create wRPC client");
-          *self.url.lock().unwrap() = rpc_config.url();
-          self.start_all_services(Some(rpc), network).await?;
-          self.connect_rpc_client().await?;
+          let encoding = rpc_config.encoding().unwrap_or(WrpcEncoding::Borsh);
+          #[cfg(not(target_arch = "wasm32"))]
+          let url = match self.secure_remote_url(&rpc_config, encoding, network).await {
+            Ok(url) => url,
+            Err(err) => {
+              log_error!("unable to connect to {}: {err}", rpc_config.url().unwrap_or_default());
+              self.notify(crate::events::Events::RemoteConnectionError { error: err.to_string() })?;
+              return Ok(false);
+            }
+          };
+          #[cfg(target_arch = "wasm32")]
+          let url = rpc_config.url();
+
+          match url {
+            // the node is checked in the background before the wallet is bound to it
+            Some(url) => {
+              let generation = self.remote_generation.load(Ordering::SeqCst);
+              let sender = self.service_events.sender.clone();
+              task::spawn(async move {
+                let verification = verify::verify_remote_node(&url, encoding, network).await.map_err(|err| err.to_string());
+                sender
+                  .try_send(WagLayladServiceEvents::RemoteNodeVerified { rpc_config, url, network, generation, verification })
+                  .ok();
+              });
+            }
+            // nodes picked by a resolver are checked once the wallet reports their server status
+            None => self.connect_remote(&rpc_config, None, encoding, network).await?,
+          }
+        }
+      }
+      WagLayladServiceEvents::RemoteNodeVerified { rpc_config, url, network, generation, verification } => {
+        if generation != self.remote_generation.load(Ordering::SeqCst) {
+          log_info!("discarding the check of {url}, the connection was changed meanwhile");
+          return Ok(false);
+        }
+
+        let display_url = rpc_config.url().unwrap_or_else(|| url.clone());
+        match verification {
+          Ok(Verification::Compatible { version, .. }) => {
+            log_info!("remote node {display_url} v{version} is compatible");
+          }
+          Ok(Verification::Incompatible(reason)) => {
+            log_warn!("refusing remote node {display_url}: {reason}");
+            #[cfg(not(target_arch = "wasm32"))]
+            self.tunnel.lock().unwrap().take();
+            *self.url.lock().unwrap() = None;
+            self.notify(crate::events::Events::RemoteNodeRejected { url: Some(display_url), reason })?;
+            return Ok(false);
+          }
+          // the wallet keeps retrying an unreachable node and its server status is checked once connected
+          Err(err) => {
+            log_warn!("unable to verify remote node {display_url}: {err}");
+          }
+        }
+
+        let encoding = rpc_config.encoding().unwrap_or(WrpcEncoding::Borsh);
+        self.connect_remote(&rpc_config, Some(url), encoding, network).await?;
+      }
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladServiceEvents::DiscoverPublicNode { network, proxy } => {
+        self.stop_all_services().await?;
+        *self.network.lock().unwrap() = network;
+        *self.url.lock().unwrap() = None;
+        manager().node_discovery().discover(network, proxy);
+      }
+      WagLayladServiceEvents::ReconnectRpc => {
+        if let Err(err) = self.disconnect_rpc().await {
+          log_warn!("error disconnecting RPC: {}", err);
         }
+        self.connect_rpc_client().await?;
+      }
+      WagLayladServiceEvents::Stop => {
+        self.stop_all_services().await?;
+        *self.url.lock().unwrap() = None;
       }
       WagLayladServiceEvents::Disable { network } => {
         self.stop_all_services().await?;
@@ -379,6 +802,14 @@ impl WagLaylaService {
         *self.url.lock().unwrap() = None;
       }
       WagLayladServiceEvents::Exit => {
+        #[cfg(not(target_arch = "wasm32"))]
+        let migration = self.migration.lock().unwrap().take();
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some((cancel, task)) = migration {
+          // let the migration discard its partial copy before shutting down
+          cancel.store(true, Ordering::Relaxed);
+          task.await.ok();
+        }
         self.stop_all_services().await?;
         return Ok(true);
       }
@@ -426,7 +857,55 @@ impl WagLaylaService {
     Ok(())
   }
 
-  pub fn create_rpc_client(url: Option<String>, resolver_urls: Option<Vec<Arc<String>>>) -> Result<Rpc> {
+  /// Checks the TLS and proxy setup of the remote endpoint before handing it to the wRPC
+  /// client, which otherwise keeps retrying without reporting why the connection fails.
+  /// Endpoints with a custom CA, a pinned certificate or a proxy are routed through a local tunnel.
+  #[cfg(not(target_arch = "wasm32"))]
+  async fn secure_remote_url(&self, rpc_config: &RpcConfig, encoding: WrpcEncoding, network: Network) -> Result<Option<String>> {
+    let proxy = rpc_config.proxy();
+    let Some(url) = rpc_config.url() else {
+      // the resolver contacts its services and the selected node directly
+      if proxy.is_some() {
+        return Err(Error::Proxy(i18n("resolver connections can not be routed through a proxy").to_string()));
+      }
+      return Ok(None);
+    };
+    let url = WaglaylaRpcClient::parse_url(url, encoding, NetworkId::from(network).into())?;
+    let trust = rpc_config.tls();
+    if !url.starts_with("wss://") && proxy.is_none() {
+      return Ok(Some(url));
+    }
+
+    let requires_tunnel = tunnel::RemoteEndpoint::requires_tunnel(&url, &trust, proxy.as_ref());
+    let endpoint = tunnel::RemoteEndpoint::try_new(&url, &trust, proxy)?;
+    // the node may come up later, so a failed connection is reported but not fatal
+    if let Err(err) = endpoint.verify().await {
+      log_warn!("{err}");
+      self.notify(crate::events::Events::RemoteConnectionError { error: err.to_string() })?;
+    }
+
+    if requires_tunnel {
+      let tunnel = endpoint.tunnel().await?;
+      let local = tunnel.url();
+      log_info!("routing {url} through {local}");
+      self.tunnel.lock().unwrap().replace(tunnel);
+      Ok(Some(local))
+    } else {
+      Ok(Some(url))
+    }
+  }
+
+  /// Binds the wallet to a wRPC client for `url`, or for a resolver-picked node if `url` is `None`
+  async fn connect_remote(self: &Arc<Self>, rpc_config: &RpcConfig, url: Option<String>, encoding: WrpcEncoding, network: Network) -> Result<()> {
+    let rpc = Self::create_rpc_client(url, rpc_config.resolver_urls(), encoding, network)
+      .expect("WagLaylad Service - unable to create wRPC client");
+    *self.url.lock().unwrap() = rpc_config.url();
+    self.start_all_services(Some(rpc), network).await?;
+    self.connect_rpc_client().await
+  }
+
+  /// Creates a wRPC client for the given encoding; urls without a port use the default port of that encoding.
+  pub fn create_rpc_client(url: Option<String>, resolver_urls: Option<Vec<Arc<String>>>, encoding: WrpcEncoding, network: Network) -> Result<Rpc> {
     let resolver_or_none = match url {
       Some(_) => None,
       None => {
@@ -440,19 +919,19 @@ impl WagLaylaService {
 
     let url = url.clone().unwrap_or_else(|| "127.0.0.1".to_string());
     let url =
-      WaglaylaRpcClient::parse_url(url, WrpcEncoding::Borsh, NetworkId::from(Network::Mainnet).into())?;
+      WaglaylaRpcClient::parse_url(url, encoding, NetworkId::from(network).into())?;
 
-    log_info!("using url {}", url);
+    log_info!("using url {} ({})", url, encoding);
 
     let wrpc_client = Arc::new(WaglaylaRpcClient::new_with_args(
-      WrpcEncoding::Borsh,
+      encoding,
       if resolver_or_none.is_some() {
         None
       } else {
         Some(url.as_str())
       },
       resolver_or_none,
-      Some(NetworkId::from(Network::Mainnet)),
+      Some(NetworkId::from(network)),
       None,
     )?);
     let rpc_ctl = wrpc_client.ctl().clone();
@@ -533,7 +1012,7 @@ impl Service for WagLaylaService {
         let _context = Context::try_from_slice(&context)?;
 
         if is_connected {
-          let network_id = network_id.unwrap_or_else(|| Network::Mainnet.into());
+          let network_id = network_id.unwrap_or_else(|| (*self.network.lock().unwrap()).into());
 
           // let event = Box::new(waglayla_wallet_core::events::Events::Connect {
           //     network_id,
@@ -686,19 +1165,32 @@ impl WagLayladServiceEvents {
       if #[cfg(not(target_arch = "wasm32"))] {
         match &node_settings.node_kind {
           WagLayladNodeKind::Disabled => {
-            Ok(WagLayladServiceEvents::Disable { network : Network::Mainnet })
+            Ok(WagLayladServiceEvents::Disable { network : node_settings.network })
           }
           WagLayladNodeKind::IntegratedAsDaemon => {
-            Ok(WagLayladServiceEvents::StartInternalAsDaemon { config : Config::from(node_settings.clone()), network : Network::Mainnet })
+            Ok(WagLayladServiceEvents::StartInternalAsDaemon { config : Config::from(node_settings.clone()), network : node_settings.network })
+          }
+          WagLayladNodeKind::ExternalAsDaemon => {
+            let path = node_settings.waglaylad_daemon_binary.trim();
+            if path.is_empty() {
+              return Err(Error::MissingExternalWagLayladBinary);
+            }
+            Ok(WagLayladServiceEvents::StartExternalAsDaemon { path : PathBuf::from(path), config : Config::from(node_settings.clone()), network : node_settings.network })
+          }
+          WagLayladNodeKind::IntegratedInProc => {
+            Ok(WagLayladServiceEvents::StartInternalInProc { config : Config::from(node_settings.clone()), network : node_settings.network })
+          }
+          WagLayladNodeKind::Remote if node_settings.connection_config_kind == NodeConnectionConfigKind::PublicServerRandom => {
+            Ok(WagLayladServiceEvents::DiscoverPublicNode { network : node_settings.network, proxy : node_settings.public_proxy() })
           }
           WagLayladNodeKind::Remote => {
-            Ok(WagLayladServiceEvents::StartRemoteConnection { rpc_config : RpcConfig::from_node_settings(node_settings,options), network : Network::Mainnet })
+            Ok(WagLayladServiceEvents::StartRemoteConnection { rpc_config : RpcConfig::from_node_settings(node_settings,options), network : node_settings.network })
           }
         }
       } else {
         match &node_settings.node_kind {
           WagLayladNodeKind::Remote => {
-            Ok(WagLayladServiceEvents::StartRemoteConnection { rpc_config : RpcConfig::from_node_settings(node_settings,options), network : Network::Mainnet })
+            Ok(WagLayladServiceEvents::StartRemoteConnection { rpc_config : RpcConfig::from_node_settings(node_settings,options), network : node_settings.network })
           }
         }
       }
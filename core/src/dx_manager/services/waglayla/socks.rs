@@ -0,0 +1,274 @@
+use crate::imports::*;
+use std::net::{IpAddr, SocketAddr};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const SOCKS_VERSION: u8 = 0x05;
+const AUTH_NONE: u8 = 0x00;
+const AUTH_PASSWORD: u8 = 0x02;
+const AUTH_UNACCEPTABLE: u8 = 0xff;
+const CMD_CONNECT: u8 = 0x01;
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_DOMAIN: u8 = 0x03;
+const ATYP_IPV6: u8 = 0x04;
+
+fn proxy_error(reason: impl Into<String>) -> Error {
+  Error::Proxy(reason.into())
+}
+
+fn reply_error(code: u8) -> Error {
+  let reason = match code {
+    0x01 => i18n("general SOCKS server failure"),
+    0x02 => i18n("connection not allowed by ruleset"),
+    0x03 => i18n("network unreachable"),
+    0x04 => i18n("host unreachable"),
+    0x05 => i18n("connection refused"),
+    0x06 => i18n("TTL expired"),
+    0x07 => i18n("command not supported"),
+    0x08 => i18n("address type not supported"),
+    _ => i18n("unknown SOCKS error"),
+  };
+  proxy_error(reason)
+}
+
+/// Opens a connection to `host:port` through a SOCKS5 proxy (RFC 1928).
+/// With `remote_dns` host names are passed to the proxy unresolved.
+pub async fn connect(proxy: &ProxySettings, host: &str, port: u16) -> Result<TcpStream> {
+  let address = proxy.address.trim();
+  let mut stream = TcpStream::connect(address)
+    .await
+    .map_err(|err| proxy_error(format!("{} {}: {}", i18n("unable to reach the proxy at"), address, err)))?;
+
+  // method negotiation
+  let authenticate = !proxy.username.is_empty();
+  let method = if authenticate { AUTH_PASSWORD } else { AUTH_NONE };
+  stream.write_all(&[SOCKS_VERSION, 1, method]).await?;
+  let mut reply = [0u8; 2];
+  stream.read_exact(&mut reply).await?;
+  if reply[0] != SOCKS_VERSION {
+    return Err(proxy_error(i18n("the proxy is not a SOCKS5 server")));
+  }
+  match reply[1] {
+    AUTH_NONE => {}
+    AUTH_PASSWORD if authenticate => {
+      // username/password sub-negotiation (RFC 1929)
+      let mut request = vec![0x01, proxy.username.len() as u8];
+      request.extend_from_slice(proxy.username.as_bytes());
+      request.push(proxy.password.len() as u8);
+      request.extend_from_slice(proxy.password.as_bytes());
+      stream.write_all(&request).await?;
+      stream.read_exact(&mut reply).await?;
+      if reply[1] != 0x00 {
+        return Err(proxy_error(i18n("the proxy rejected the username or password")));
+      }
+    }
+    AUTH_UNACCEPTABLE => {
+      return Err(proxy_error(i18n("the proxy requires an unsupported authentication method")));
+    }
+    _ => {
+      return Err(proxy_error(i18n("unexpected SOCKS authentication reply")));
+    }
+  }
+
+  // connect request
+  let target = match host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+    Ok(ip) => Some(SocketAddr::new(ip, port)),
+    Err(_) if proxy.remote_dns => None,
+    Err(_) => Some(
+      tokio::net::lookup_host((host, port))
+        .await?
+        .next()
+        .ok_or_else(|| Error::custom(format!("{} {}", i18n("Unable to resolve"), host)))?,
+    ),
+  };
+
+  let mut request = vec![SOCKS_VERSION, CMD_CONNECT, 0x00];
+  match target {
+    Some(SocketAddr::V4(addr)) => {
+      request.push(ATYP_IPV4);
+      request.extend_from_slice(&addr.ip().octets());
+    }
+    Some(SocketAddr::V6(addr)) => {
+      request.push(ATYP_IPV6);
+      request.extend_from_slice(&addr.ip().octets());
+    }
+    None => {
+      if host.len() > 255 {
+        return Err(Error::InvalidUrl(host.to_string()));
+      }
+      request.push(ATYP_DOMAIN);
+      request.push(host.len() as u8);
+      request.extend_from_slice(host.as_bytes());
+    }
+  }
+  request.extend_from_slice(&port.to_be_bytes());
+  stream.write_all(&request).await?;
+
+  let mut header = [0u8; 4];
+  stream.read_exact(&mut header).await?;
+  if header[1] != 0x00 {
+    return Err(reply_error(header[1]));
+  }
+
+  // skip the bound address reported by the proxy
+  let len = match header[3] {
+    ATYP_IPV4 => 4,
+    ATYP_IPV6 => 16,
+    ATYP_DOMAIN => {
+      let mut len = [0u8; 1];
+      stream.read_exact(&mut len).await?;
+      len[0] as usize
+    }
+    _ => return Err(reply_error(0x08)),
+  };
+  let mut bound = vec![0u8; len + 2];
+  stream.read_exact(&mut bound).await?;
+
+  Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::net::TcpListener;
+  use tokio::task::JoinHandle;
+
+  async fn read_field(stream: &mut TcpStream) -> Vec<u8> {
+    let len = stream.read_u8().await.unwrap();
+    let mut field = vec![0u8; len as usize];
+    stream.read_exact(&mut field).await.unwrap();
+    field
+  }
+
+  /// SOCKS5 stand-in serving a single connection with the given reply code. Yields the
+  /// credentials and the connect request it received; on success it then sends `pong`.
+  async fn stand_in(reply: u8) -> (String, JoinHandle<(Option<(Vec<u8>, Vec<u8>)>, Vec<u8>)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let server = tokio::spawn(async move {
+      let (mut stream, _) = listener.accept().await.unwrap();
+      let mut greeting = [0u8; 3];
+      stream.read_exact(&mut greeting).await.unwrap();
+      assert_eq!(greeting[..2], [SOCKS_VERSION, 1]);
+      stream.write_all(&[SOCKS_VERSION, greeting[2]]).await.unwrap();
+
+      let credentials = if greeting[2] == AUTH_PASSWORD {
+        assert_eq!(stream.read_u8().await.unwrap(), 0x01);
+        let username = read_field(&mut stream).await;
+        let password = read_field(&mut stream).await;
+        stream.write_all(&[0x01, 0x00]).await.unwrap();
+        Some((username, password))
+      } else {
+        None
+      };
+
+      let mut request = vec![0u8; 4];
+      stream.read_exact(&mut request).await.unwrap();
+      let len = match request[3] {
+        ATYP_IPV4 => 4,
+        ATYP_IPV6 => 16,
+        ATYP_DOMAIN => {
+          let len = stream.read_u8().await.unwrap();
+          request.push(len);
+          len as usize
+        }
+        atyp => panic!("unexpected address type {atyp}"),
+      };
+      let mut address = vec![0u8; len + 2];
+      stream.read_exact(&mut address).await.unwrap();
+      request.extend_from_slice(&address);
+
+      stream.write_all(&[SOCKS_VERSION, reply, 0x00, ATYP_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap();
+      if reply == 0x00 {
+        stream.write_all(b"pong").await.unwrap();
+      }
+      (credentials, request)
+    });
+
+    (address, server)
+  }
+
+  async fn read_pong(mut stream: TcpStream) {
+    let mut pong = [0u8; 4];
+    stream.read_exact(&mut pong).await.unwrap();
+    assert_eq!(&pong, b"pong");
+  }
+
+  #[tokio::test]
+  async fn connects_without_authentication() {
+    let (address, server) = stand_in(0x00).await;
+    let proxy = ProxySettings { address, ..Default::default() };
+
+    let stream = connect(&proxy, "10.1.2.3", 16110).await.unwrap();
+    read_pong(stream).await;
+
+    let (credentials, request) = server.await.unwrap();
+    assert_eq!(credentials, None);
+    assert_eq!(request, [SOCKS_VERSION, CMD_CONNECT, 0x00, ATYP_IPV4, 10, 1, 2, 3, 0x3e, 0xee]);
+  }
+
+  #[tokio::test]
+  async fn authenticates_with_username_and_password() {
+    let (address, server) = stand_in(0x00).await;
+    let proxy = ProxySettings {
+      address,
+      username: "user".to_string(),
+      password: "secret".to_string(),
+      ..Default::default()
+    };
+
+    let stream = connect(&proxy, "[::1]", 443).await.unwrap();
+    read_pong(stream).await;
+
+    let (credentials, request) = server.await.unwrap();
+    assert_eq!(credentials, Some((b"user".to_vec(), b"secret".to_vec())));
+    assert_eq!(request[3], ATYP_IPV6);
+    assert_eq!(request[4..20], std::net::Ipv6Addr::LOCALHOST.octets());
+    assert_eq!(request[20..], 443u16.to_be_bytes());
+  }
+
+  #[tokio::test]
+  async fn passes_host_names_to_the_proxy_with_remote_dns() {
+    let (address, server) = stand_in(0x00).await;
+    let proxy = ProxySettings { address, remote_dns: true, ..Default::default() };
+
+    let stream = connect(&proxy, "node.example.onion", 17110).await.unwrap();
+    read_pong(stream).await;
+
+    let (_, request) = server.await.unwrap();
+    let mut expected = vec![SOCKS_VERSION, CMD_CONNECT, 0x00, ATYP_DOMAIN, 18];
+    expected.extend_from_slice(b"node.example.onion");
+    expected.extend_from_slice(&17110u16.to_be_bytes());
+    assert_eq!(request, expected);
+  }
+
+  #[tokio::test]
+  async fn resolves_host_names_locally_without_remote_dns() {
+    let (address, server) = stand_in(0x00).await;
+    let proxy = ProxySettings { address, remote_dns: false, ..Default::default() };
+
+    let stream = connect(&proxy, "localhost", 17110).await.unwrap();
+    read_pong(stream).await;
+
+    let (_, request) = server.await.unwrap();
+    let ip = match request[3] {
+      ATYP_IPV4 => IpAddr::from(<[u8; 4]>::try_from(&request[4..8]).unwrap()),
+      ATYP_IPV6 => IpAddr::from(<[u8; 16]>::try_from(&request[4..20]).unwrap()),
+      atyp => panic!("expected a resolved address, got address type {atyp}"),
+    };
+    assert!(ip.is_loopback());
+    assert_eq!(request[request.len() - 2..], 17110u16.to_be_bytes());
+  }
+
+  #[tokio::test]
+  async fn reports_the_proxy_reply_code() {
+    let (address, server) = stand_in(0x05).await;
+    let proxy = ProxySettings { address, ..Default::default() };
+
+    let err = connect(&proxy, "10.1.2.3", 16110).await.unwrap_err();
+    assert!(matches!(err, Error::Proxy(_)));
+    assert_eq!(err.to_string(), reply_error(0x05).to_string());
+    server.await.unwrap();
+  }
+}
@@ -0,0 +1,370 @@
+use crate::imports::*;
+use super::socks;
+use sha2::{Digest, Sha256};
+use std::net::{Ipv4Addr, SocketAddr};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio_native_tls::{native_tls, TlsConnector};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+trait Transport: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Transport for T {}
+
+/// Scheme, host, port and path of a url as produced by `WaglaylaRpcClient::parse_url()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Endpoint {
+  secure: bool,
+  host: String,
+  port: u16,
+  path: String,
+}
+
+impl Endpoint {
+  fn parse(url: &str) -> Result<Self> {
+    let (secure, rest) = if let Some(rest) = url.strip_prefix("wss://") {
+      (true, rest)
+    } else if let Some(rest) = url.strip_prefix("ws://") {
+      (false, rest)
+    } else {
+      return Err(Error::InvalidUrl(format!("{url} (expected a ws:// or wss:// url)")));
+    };
+
+    let (authority, path) = match rest.find('/') {
+      Some(index) => (&rest[..index], &rest[index..]),
+      None => (rest, "/"),
+    };
+
+    let (host, port) = if let Some(ipv6) = authority.strip_prefix('[') {
+      let (host, port) = ipv6.split_once(']').ok_or_else(|| Error::InvalidUrl(url.to_string()))?;
+      let port = match port {
+        "" => None,
+        port => Some(port.strip_prefix(':').ok_or_else(|| Error::InvalidUrl(url.to_string()))?),
+      };
+      (host, port)
+    } else {
+      match authority.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (authority, None),
+      }
+    };
+
+    let port = match port {
+      Some(port) => port.parse::<u16>().map_err(|_| Error::InvalidUrl(url.to_string()))?,
+      None if secure => 443,
+      None => 80,
+    };
+
+    if host.is_empty() {
+      return Err(Error::InvalidUrl(url.to_string()));
+    }
+
+    Ok(Self {
+      secure,
+      host: host.to_string(),
+      port,
+      path: path.to_string(),
+    })
+  }
+
+  /// Value of the `Host` header expected by the remote server
+  fn authority(&self) -> String {
+    let host = if self.host.contains(':') { format!("[{}]", self.host) } else { self.host.clone() };
+    let default_port = if self.secure { 443 } else { 80 };
+    if self.port == default_port { host } else { format!("{}:{}", host, self.port) }
+  }
+
+  fn tls_error(&self, reason: impl AsRef<str>) -> Error {
+    Error::Tls { host: self.authority(), reason: explain(reason.as_ref()) }
+  }
+}
+
+/// Appends the likely cause to the handshake errors reported by the platform TLS library
+fn explain(reason: &str) -> String {
+  let lower = reason.to_lowercase();
+  let hint = if lower.contains("wrong version number") || lower.contains("packet length too long") || lower.contains("record layer failure") {
+    Some(i18n("the endpoint does not speak TLS, use ws:// instead of wss://"))
+  } else if lower.contains("expired") || lower.contains("not yet valid") {
+    Some(i18n("the server certificate is expired or not yet valid, check the certificate and the system clock"))
+  } else if lower.contains("hostname mismatch") || lower.contains("not valid for") || lower.contains("does not match") {
+    Some(i18n("the server certificate does not match the host name in the url"))
+  } else if lower.contains("self signed") || lower.contains("self-signed") || lower.contains("local issuer")
+    || lower.contains("unknown ca") || lower.contains("not trusted") || lower.contains("untrusted") || lower.contains("verify failed")
+  {
+    Some(i18n("the server certificate is not trusted, configure its CA certificate or pin its fingerprint"))
+  } else if lower.contains("handshake failure") || lower.contains("protocol version") {
+    Some(i18n("the server does not support a compatible TLS version or cipher"))
+  } else {
+    None
+  };
+
+  match hint {
+    Some(hint) => format!("{reason} ({hint})"),
+    None => reason.to_string(),
+  }
+}
+
+/// Connection settings for a remote wRPC endpoint that the wRPC client can
+/// not handle on its own: a custom CA, a pinned certificate or a SOCKS5 proxy.
+pub struct RemoteEndpoint {
+  endpoint: Arc<Endpoint>,
+  connector: Option<Arc<TlsConnector>>,
+  fingerprint: Option<[u8; 32]>,
+  proxy: Option<Arc<ProxySettings>>,
+}
+
+impl RemoteEndpoint {
+  pub fn try_new(url: &str, trust: &TlsTrust, proxy: Option<ProxySettings>) -> Result<Self> {
+    let endpoint = Endpoint::parse(url)?;
+    let fingerprint = endpoint.secure.then(|| trust.fingerprint()).transpose()?.flatten();
+
+    let connector = if endpoint.secure {
+      let mut builder = native_tls::TlsConnector::builder();
+      let ca_file = trust.ca_file.trim();
+      if !ca_file.is_empty() {
+        let ca_error = |reason: String| Error::TlsCaCertificate { path: ca_file.to_string(), reason };
+        let pem = std::fs::read(ca_file).map_err(|err| ca_error(err.to_string()))?;
+        let certificate = native_tls::Certificate::from_pem(&pem).map_err(|err| ca_error(err.to_string()))?;
+        builder.add_root_certificate(certificate);
+      } else if fingerprint.is_some() {
+        // a pinned certificate identifies the server on its own, e.g. a self-signed one
+        builder.danger_accept_invalid_certs(true);
+      }
+      let connector = builder.build().map_err(|err| endpoint.tls_error(err.to_string()))?;
+      Some(Arc::new(TlsConnector::from(connector)))
+    } else {
+      None
+    };
+
+    if let Some(proxy) = proxy.as_ref() {
+      proxy.validate().map_err(|err| Error::Proxy(err.to_string()))?;
+    }
+
+    Ok(Self {
+      endpoint: Arc::new(endpoint),
+      connector,
+      fingerprint,
+      proxy: proxy.map(Arc::new),
+    })
+  }
+
+  /// `true` if the wRPC client has to reach the endpoint through [`RemoteEndpoint::tunnel()`]
+  pub fn requires_tunnel(url: &str, trust: &TlsTrust, proxy: Option<&ProxySettings>) -> bool {
+    proxy.is_some() || (url.starts_with("wss://") && trust.is_custom())
+  }
+
+  /// Connects to the server, reporting why the TLS handshake or the proxy failed
+  pub async fn verify(&self) -> Result<()> {
+    connect(&self.endpoint, self.connector.as_deref(), self.fingerprint.as_ref(), self.proxy.as_deref()).await.map(|_| ())
+  }
+
+  /// Starts a local plaintext listener forwarding the wRPC connection to the server.
+  /// The wRPC client only supports direct connections trusting the system certificates,
+  /// so endpoints with a custom CA, a pinned certificate or a proxy are reached through
+  /// the returned tunnel.
+  pub async fn tunnel(&self) -> Result<Tunnel> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+    let local = listener.local_addr()?;
+
+    let endpoint = self.endpoint.clone();
+    let connector = self.connector.clone();
+    let fingerprint = self.fingerprint;
+    let proxy = self.proxy.clone();
+    let task = tokio::spawn(async move {
+      while let Ok((inbound, _)) = listener.accept().await {
+        let endpoint = endpoint.clone();
+        let connector = connector.clone();
+        let proxy = proxy.clone();
+        tokio::spawn(async move {
+          if let Err(err) = forward(inbound, &endpoint, connector.as_deref(), fingerprint.as_ref(), proxy.as_deref()).await {
+            log_warn!("tunnel to {}: {}", endpoint.authority(), err);
+          }
+        });
+      }
+    });
+
+    Ok(Tunnel {
+      local,
+      path: self.endpoint.path.clone(),
+      task,
+    })
+  }
+}
+
+/// Local end of a [`RemoteEndpoint::tunnel()`]; the listener stops when dropped
+pub struct Tunnel {
+  local: SocketAddr,
+  path: String,
+  task: JoinHandle<()>,
+}
+
+impl Tunnel {
+  /// `ws://` url the wRPC client connects to
+  pub fn url(&self) -> String {
+    format!("ws://{}{}", self.local, self.path)
+  }
+}
+
+impl Drop for Tunnel {
+  fn drop(&mut self) {
+    self.task.abort();
+  }
+}
+
+async fn connect(
+  endpoint: &Endpoint,
+  connector: Option<&TlsConnector>,
+  fingerprint: Option<&[u8; 32]>,
+  proxy: Option<&ProxySettings>,
+) -> Result<Box<dyn Transport>> {
+  let handshake = async {
+    let stream = match proxy {
+      Some(proxy) => socks::connect(proxy, &endpoint.host, endpoint.port).await?,
+      None => TcpStream::connect((endpoint.host.as_str(), endpoint.port))
+        .await
+        .map_err(|err| Error::custom(format!("{} {}: {}", i18n("Unable to reach"), endpoint.authority(), err)))?,
+    };
+
+    let Some(connector) = connector else {
+      return Ok(Box::new(stream) as Box<dyn Transport>);
+    };
+
+    let stream = connector
+      .connect(&endpoint.host, stream)
+      .await
+      .map_err(|err| endpoint.tls_error(err.to_string()))?;
+
+    if let Some(expected) = fingerprint {
+      let certificate = stream
+        .get_ref()
+        .peer_certificate()
+        .map_err(|err| endpoint.tls_error(err.to_string()))?
+        .ok_or_else(|| endpoint.tls_error(i18n("the server did not present a certificate")))?;
+      let der = certificate.to_der().map_err(|err| endpoint.tls_error(err.to_string()))?;
+      let received: [u8; 32] = Sha256::digest(&der).into();
+      if &received != expected {
+        return Err(Error::TlsFingerprintMismatch {
+          host: endpoint.authority(),
+          expected: format_fingerprint(expected),
+          received: format_fingerprint(&received),
+        });
+      }
+    }
+
+    Ok(Box::new(stream) as Box<dyn Transport>)
+  };
+
+  tokio::time::timeout(CONNECT_TIMEOUT, handshake)
+    .await
+    .map_err(|_| Error::custom(format!("{} {}", i18n("Connection timed out:"), endpoint.authority())))?
+}
+
+/// Replaces the `Host` header of a request head with the authority of the remote server
+fn rewrite_host(head: &[u8], endpoint: &Endpoint) -> String {
+  String::from_utf8_lossy(head)
+    .split("\r\n")
+    .map(|line| {
+      if line.to_ascii_lowercase().starts_with("host:") {
+        format!("Host: {}", endpoint.authority())
+      } else {
+        line.to_string()
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\r\n")
+}
+
+/// Relays one client connection, pointing the `Host` header of the
+/// websocket upgrade request at the remote server.
+async fn forward(
+  mut inbound: TcpStream,
+  endpoint: &Endpoint,
+  connector: Option<&TlsConnector>,
+  fingerprint: Option<&[u8; 32]>,
+  proxy: Option<&ProxySettings>,
+) -> Result<()> {
+  let mut head = Vec::new();
+  let mut buffer = [0u8; 1024];
+  let end = loop {
+    if let Some(index) = head.windows(4).position(|window| window == b"\r\n\r\n") {
+      break index + 4;
+    }
+    if head.len() > MAX_REQUEST_HEAD {
+      return Err(Error::custom("websocket upgrade request is too large"));
+    }
+    let len = inbound.read(&mut buffer).await?;
+    if len == 0 {
+      return Ok(());
+    }
+    head.extend_from_slice(&buffer[..len]);
+  };
+
+  let request = rewrite_host(&head[..end], endpoint);
+  let mut outbound = connect(endpoint, connector, fingerprint, proxy).await?;
+  outbound.write_all(request.as_bytes()).await?;
+  outbound.write_all(&head[end..]).await?;
+  tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn endpoint(secure: bool, host: &str, port: u16, path: &str) -> Endpoint {
+    Endpoint { secure, host: host.to_string(), port, path: path.to_string() }
+  }
+
+  #[test]
+  fn endpoint_parses_hosts_ports_and_paths() {
+    assert_eq!(Endpoint::parse("ws://127.0.0.1:17110").unwrap(), endpoint(false, "127.0.0.1", 17110, "/"));
+    assert_eq!(Endpoint::parse("wss://node.example.com/waglayla/mainnet").unwrap(), endpoint(true, "node.example.com", 443, "/waglayla/mainnet"));
+    assert_eq!(Endpoint::parse("ws://node.example.com/").unwrap(), endpoint(false, "node.example.com", 80, "/"));
+    assert_eq!(Endpoint::parse("wss://node.example.com:8443/ws?x=1").unwrap(), endpoint(true, "node.example.com", 8443, "/ws?x=1"));
+  }
+
+  #[test]
+  fn endpoint_parses_ipv6_hosts() {
+    assert_eq!(Endpoint::parse("ws://[::1]:17110").unwrap(), endpoint(false, "::1", 17110, "/"));
+    assert_eq!(Endpoint::parse("wss://[2001:db8::1]/ws").unwrap(), endpoint(true, "2001:db8::1", 443, "/ws"));
+  }
+
+  #[test]
+  fn endpoint_rejects_invalid_urls() {
+    for url in [
+      "127.0.0.1:17110",
+      "http://node.example.com",
+      "ws://",
+      "ws://:17110",
+      "ws://node.example.com:",
+      "ws://node.example.com:port",
+      "ws://node.example.com:99999",
+      "ws://[::1",
+      "ws://[::1]17110",
+      "ws://[]:17110",
+    ] {
+      assert!(Endpoint::parse(url).is_err(), "{url}");
+    }
+  }
+
+  #[test]
+  fn authority_omits_default_ports() {
+    assert_eq!(endpoint(false, "node.example.com", 80, "/").authority(), "node.example.com");
+    assert_eq!(endpoint(true, "node.example.com", 443, "/").authority(), "node.example.com");
+    assert_eq!(endpoint(true, "node.example.com", 80, "/").authority(), "node.example.com:80");
+    assert_eq!(endpoint(false, "127.0.0.1", 17110, "/").authority(), "127.0.0.1:17110");
+    assert_eq!(endpoint(true, "::1", 443, "/").authority(), "[::1]");
+    assert_eq!(endpoint(false, "::1", 17110, "/").authority(), "[::1]:17110");
+  }
+
+  #[test]
+  fn rewrite_host_points_the_request_at_the_remote_server() {
+    let head = b"GET /ws HTTP/1.1\r\nhost: 127.0.0.1:45678\r\nUpgrade: websocket\r\nX-Host: kept\r\n\r\n";
+    assert_eq!(
+      rewrite_host(head, &endpoint(true, "node.example.com", 443, "/ws")),
+      "GET /ws HTTP/1.1\r\nHost: node.example.com\r\nUpgrade: websocket\r\nX-Host: kept\r\n\r\n"
+    );
+  }
+}
@@ -0,0 +1,115 @@
+use crate::imports::*;
+use waglayla_wallet_core::rpc::{ConnectOptions, ConnectStrategy, WrpcEncoding};
+
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reason a remote node can not serve the wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeIncompatibility {
+  WrongNetwork { expected: NetworkId, actual: NetworkId },
+  UtxoIndexNotEnabled,
+  UnsupportedVersion { version: String },
+}
+
+impl std::fmt::Display for NodeIncompatibility {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      NodeIncompatibility::WrongNetwork { expected, actual } => {
+        write!(f, "{} {} ({} {})", i18n("The node is on"), actual, i18n("expected"), expected)
+      }
+      NodeIncompatibility::UtxoIndexNotEnabled => write!(f, "{}", i18n("The node does not have the UTXO index enabled")),
+      NodeIncompatibility::UnsupportedVersion { version } => {
+        write!(f, "{} {} ({} {})", i18n("Unsupported node version"), version, i18n("requires"), compatible_versions())
+      }
+    }
+  }
+}
+
+impl NodeIncompatibility {
+  /// What the user can do about it
+  pub fn remedy(&self) -> &'static str {
+    match self {
+      NodeIncompatibility::WrongNetwork { .. } => {
+        i18n("Select the network served by this node, or connect to a node of the selected network.")
+      }
+      NodeIncompatibility::UtxoIndexNotEnabled => {
+        i18n("Wallet balances and transactions require a node started with --utxoindex. Enable it on the node or connect to another one.")
+      }
+      NodeIncompatibility::UnsupportedVersion { .. } => {
+        i18n("The node speaks a different protocol version than this wallet. Upgrade the node or connect to another one.")
+      }
+    }
+  }
+}
+
+/// Outcome of [`verify_remote_node()`]
+#[derive(Debug, Clone)]
+pub enum Verification {
+  Compatible { is_synced: bool, version: String },
+  Incompatible(NodeIncompatibility),
+}
+
+/// A node is considered compatible when its major and minor
+/// version match the node libraries this application is built against.
+pub fn is_compatible_version(version: &str) -> bool {
+  let major_minor = |version: &str| version.split('.').take(2).map(str::to_string).collect::<Vec<_>>();
+  major_minor(version.trim_start_matches('v')) == major_minor(&waglayla_wallet_core::version())
+}
+
+fn compatible_versions() -> String {
+  let version = waglayla_wallet_core::version();
+  let major_minor = version.split('.').take(2).collect::<Vec<_>>().join(".");
+  format!("{major_minor}.x")
+}
+
+/// Checks the server info reported by a node against the selected network.
+/// `has_utxo_index` is `None` where the node does not report it; the wallet then
+/// raises `UtxoIndexNotEnabled` on its own once it is bound to the node.
+pub fn check_server(
+  network_id: NetworkId,
+  has_utxo_index: Option<bool>,
+  server_version: &str,
+  expected: NetworkId,
+) -> Option<NodeIncompatibility> {
+  if network_id != expected {
+    Some(NodeIncompatibility::WrongNetwork { expected, actual: network_id })
+  } else if has_utxo_index == Some(false) {
+    Some(NodeIncompatibility::UtxoIndexNotEnabled)
+  } else if !is_compatible_version(server_version) {
+    Some(NodeIncompatibility::UnsupportedVersion { version: server_version.to_string() })
+  } else {
+    None
+  }
+}
+
+/// Connects a throw-away client to `url` and checks the server info of the node
+/// before the wallet is bound to it. Errors mean the node could not be queried.
+pub async fn verify_remote_node(url: &str, encoding: WrpcEncoding, network: Network) -> Result<Verification> {
+  let network_id = NetworkId::from(network);
+  let verify = async {
+    let url = WaglaylaRpcClient::parse_url(url.to_string(), encoding, network_id.into())?;
+    let client = WaglaylaRpcClient::new_with_args(encoding, Some(url.as_str()), None, Some(network_id), None)?;
+    let options = ConnectOptions {
+      block_async_connect: true,
+      strategy: ConnectStrategy::Fallback,
+      url: None,
+      connect_timeout: Some(VERIFY_TIMEOUT),
+      retry_interval: None,
+    };
+    client.connect(Some(options)).await?;
+    let info = client.get_server_info().await;
+    client.disconnect().await.ok();
+    let info = info?;
+
+    let verification = match check_server(info.network_id, Some(info.has_utxo_index), &info.server_version, network_id) {
+      Some(incompatibility) => Verification::Incompatible(incompatibility),
+      None => Verification::Compatible { is_synced: info.is_synced, version: info.server_version },
+    };
+    Ok::<_, Error>(verification)
+  };
+
+  select! {
+    verification = verify.fuse() => verification,
+    _ = task::sleep(VERIFY_TIMEOUT * 2).fuse() => Err(Error::custom(i18n("Timed out querying the node"))),
+  }
+}
@@ -201,7 +201,8 @@ impl Transaction {
       ).on_hover_cursor(egui::CursorIcon::PointingHand);
 
       if response.clicked() {
-        let link = format!("https://explorer.waglayla.com/txs/{}", self.id().to_string());
+        let network = Network::from(*self.context().record.network_id());
+        let link = format!("{}/txs/{}", network.explorer(), self.id().to_string());
         if let Err(err) = open::that(&link) {
           log_error!("Failed to open URL: {}", err);
         }
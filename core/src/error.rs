@@ -50,6 +50,21 @@ pub enum Error {
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
 
+    #[error("TLS connection to {host} failed: {reason}")]
+    Tls { host: String, reason: String },
+
+    #[error("Certificate fingerprint mismatch for {host} (expected {expected}, received {received})")]
+    TlsFingerprintMismatch { host: String, expected: String, received: String },
+
+    #[error("Unable to load CA certificate '{path}': {reason}")]
+    TlsCaCertificate { path: String, reason: String },
+
+    #[error("Invalid certificate fingerprint: expected 64 hexadecimal digits")]
+    InvalidTlsFingerprint,
+
+    #[error("Proxy error: {0}")]
+    Proxy(String),
+
     #[error(transparent)]
     RpcError(#[from] waglayla_rpc_core::error::RpcError),
 
@@ -59,6 +74,12 @@ pub enum Error {
     #[error("Unable to acquire node stdout handle")]
     NodeStdoutHandleError,
 
+    #[error("Invalid node arguments: {0}")]
+    InvalidNodeArgs(String),
+
+    #[error("Node argument '{0}' is managed by the node settings")]
+    ReservedNodeArg(String),
+
     #[error("Metrics: {0}")]
     Metrics(#[from] waglayla_metrics_core::error::Error),
 
@@ -80,6 +101,12 @@ pub enum Error {
     #[error("Invalid network type")]
     InvalidNetworkType,
 
+    #[error("Insufficient disk space ({} MB required, {} MB available)", .required / 1_000_000, .available / 1_000_000)]
+    InsufficientDiskSpace { required: u64, available: u64 },
+
+    #[error("Data folder migration failed: {0}")]
+    StorageMigration(String),
+
     #[error("Invalid network '{0}'")]
     InvalidNetwork(String),
 
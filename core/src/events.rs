@@ -20,6 +20,46 @@ pub enum Events {
   HashrateUpdate(u64),
   DifficultyUpdate(u64),
   MempoolUpdate(usize),
+  DaemonStarted {
+    crashes: u32,
+  },
+  DaemonCrash {
+    crashes: u32,
+    status: String,
+    restart_in: Option<Duration>,
+  },
+  DaemonStopping,
+  DaemonStopped,
+  DaemonVersion {
+    version: String,
+    compatible: bool,
+  },
+  DaemonStartError {
+    error: String,
+  },
+  StorageMigration(crate::storage::StorageMigration),
+  DiskSpace(crate::storage::DiskSpace),
+  NodeLog(crate::node_log::NodeLogEvent),
+  RemoteEndpoints(Box<crate::dx_manager::services::FailoverReport>),
+  RemoteFailover {
+    url: String,
+    reason: String,
+  },
+  RemoteConnectionError {
+    error: String,
+  },
+  RemoteNodeRejected {
+    url: Option<String>,
+    reason: crate::dx_manager::services::NodeIncompatibility,
+  },
+  NodeDiscovery(crate::dx_manager::services::DiscoveryStatus),
+  BridgeMetrics(Box<crate::dx_manager::services::BridgeMetricsReport>),
+  BridgeReconfigure(crate::dx_manager::services::ReconfigureStatus),
+  BridgeNode(Option<crate::dx_manager::services::BridgeNodeStatus>),
+  NodeHealth {
+    state: crate::dx_manager::services::HealthState,
+    remediation: Option<HealthRemediation>,
+  },
   Notify(&'static str, ToastKind, u64),
   // VersionUpdate(Release),
   ThemeChange,
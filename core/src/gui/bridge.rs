@@ -0,0 +1,83 @@
+use crate::imports::*;
+use std::net::SocketAddr;
+
+/// Text fields of the typed [`BridgeSettings`] while they are being edited
+#[derive(Default)]
+pub struct BridgeSettingsEditor {
+  pub stratum_port: String,
+  pub prom_port: String,
+  pub block_wait_time: String,
+  // listen addresses probed last and the outcome, so ports are not bound on every frame
+  availability: Option<(Vec<SocketAddr>, Option<String>)>,
+  // outcome of the last remote node connection test, `None` while it runs
+  connection_test: Option<(String, Arc<Mutex<Option<std::result::Result<(), String>>>>)>,
+}
+
+impl From<&BridgeSettings> for BridgeSettingsEditor {
+  fn from(settings: &BridgeSettings) -> Self {
+    BridgeSettingsEditor {
+      stratum_port: format_listen_address(&settings.stratum_port),
+      prom_port: format_listen_address(&settings.prom_port),
+      block_wait_time: format_go_duration(&settings.block_wait_time),
+      availability: None,
+      connection_test: None,
+    }
+  }
+}
+
+impl BridgeSettingsEditor {
+  /// Parses the edited fields into `settings`
+  pub fn apply(&self, settings: &mut BridgeSettings) -> std::result::Result<(), String> {
+    settings.stratum_port = parse_listen_address(&self.stratum_port)?;
+    settings.prom_port = parse_listen_address(&self.prom_port)?;
+    settings.block_wait_time = parse_go_duration(&self.block_wait_time)?;
+    Ok(())
+  }
+
+  /// Checks that the listen addresses of `settings` can be bound. Addresses
+  /// held by the running bridge (`active`) are skipped.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn check_availability(&mut self, settings: &BridgeSettings, active: Option<&BridgeSettings>) -> Option<String> {
+    let held = active.map(|active| vec![active.stratum_port, active.prom_port]).unwrap_or_default();
+    let addresses = [settings.stratum_port, settings.prom_port]
+      .into_iter()
+      .filter(|address| !held.contains(address))
+      .collect::<Vec<_>>();
+
+    match self.availability.as_ref() {
+      Some((checked, result)) if *checked == addresses => result.clone(),
+      _ => {
+        let result = addresses.iter().find_map(|address| {
+          std::net::TcpListener::bind(address)
+            .err()
+            .map(|err| format!("{} {}: {}", i18n("Port is not available"), format_listen_address(address), err))
+        });
+        self.availability = Some((addresses, result.clone()));
+        result
+      }
+    }
+  }
+
+  /// Tests the connection to the remote node at `address` in the background
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn test_connection(&mut self, address: &str) {
+    let result = Arc::new(Mutex::new(None));
+    self.connection_test = Some((address.to_string(), result.clone()));
+
+    let address = address.to_string();
+    tokio::spawn(async move {
+      let outcome = crate::dx_manager::services::bridge::check_node_connection(&address).await;
+      *result.lock().unwrap() = Some(outcome);
+      manager().request_repaint();
+    });
+  }
+
+  /// Result of the connection test for `address`; a test of another address is not reported
+  pub fn connection_test(&self, address: &str) -> Option<Option<std::result::Result<(), String>>> {
+    self
+      .connection_test
+      .as_ref()
+      .filter(|(tested, _)| tested == address)
+      .map(|(_, result)| result.lock().unwrap().clone())
+  }
+}
@@ -25,4 +25,6 @@ pub use frame::*;
 pub mod pagination;
 pub use pagination::*;
 
-pub use network::NetworkInterfaceEditor;
\ No newline at end of file
+pub use network::NetworkInterfaceEditor;
+pub mod bridge;
+pub use bridge::BridgeSettingsEditor;
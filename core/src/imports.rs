@@ -100,7 +100,9 @@ pub use crate::dx_manager::channel::{ DaemonMessage };
 pub use crate::settings::{
   WagLayladNodeKind, NetworkInterfaceConfig, NetworkInterfaceKind,
   NodeConnectionConfigKind, NodeSettings, RpcConfig, RpcOptions, Settings,
-  UserInterfaceSettings,
+  UserInterfaceSettings, LogSettings, HealthSettings, HealthRemediation, TlsTrust, ProxySettings,
+  format_fingerprint, BridgeSettings, parse_listen_address, format_listen_address,
+  parse_go_duration, format_go_duration,
 };
 pub use crate::node_state::NodeState;
 // pub use crate::status::Status;
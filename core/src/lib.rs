@@ -27,6 +27,7 @@ pub mod fonts;
 pub mod network;
 pub mod collection;
 pub mod node_state;
+pub mod node_log;
 
 pub mod assets;
 pub mod platform;
\ No newline at end of file
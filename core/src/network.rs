@@ -178,6 +178,28 @@ impl Network {
         }
     }
 
+    pub fn explorer(&self) -> &'static str {
+        match self {
+            Network::Mainnet => MAINNET_EXPLORER,
+            Network::Testnet10 => TESTNET10_EXPLORER,
+            Network::Testnet11 => TESTNET11_EXPLORER,
+        }
+    }
+
+    /// Name of the node data folder for this network inside the node application directory
+    pub fn data_folder(&self) -> String {
+        format!("data-{}", self)
+    }
+
+    /// Arguments selecting this network on the waglaylad command line
+    pub fn daemon_args(&self) -> Vec<String> {
+        match self {
+            Network::Mainnet => vec![],
+            Network::Testnet10 => vec!["--testnet".to_string(), "--netsuffix=10".to_string()],
+            Network::Testnet11 => vec!["--testnet".to_string(), "--netsuffix=11".to_string()],
+        }
+    }
+
     pub fn tps(&self) -> u64 {
         let params = Params::from(*self);
         params.max_block_mass / BASIC_TRANSACTION_MASS * params.bps()
@@ -0,0 +1,319 @@
+use crate::imports::*;
+
+/// Stage of the initial block download as reported by the node log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IbdStage {
+  Started,
+  Proof,
+  Headers,
+  TrustSync,
+  UtxoSet,
+  Blocks,
+  Completed,
+}
+
+impl std::fmt::Display for IbdStage {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      IbdStage::Started => write!(f, "{}", i18n("Starting IBD")),
+      IbdStage::Proof => write!(f, "{}", i18n("Processing Proofs")),
+      IbdStage::Headers => write!(f, "{}", i18n("Syncing Headers")),
+      IbdStage::TrustSync => write!(f, "{}", i18n("Validating")),
+      IbdStage::UtxoSet => write!(f, "{}", i18n("Syncing UTXO Set")),
+      IbdStage::Blocks => write!(f, "{}", i18n("Processing Blocks")),
+      IbdStage::Completed => write!(f, "{}", i18n("IBD Completed")),
+    }
+  }
+}
+
+/// Typed representation of the waglaylad log lines the UI cares about
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeLogEvent {
+  Ibd { stage: IbdStage, progress: Option<u8> },
+  Processed { blocks: u64, headers: u64 },
+  PeerConnected { address: String },
+  PeerDisconnected { address: String },
+  Upnp { success: bool, message: String },
+  Error { message: String },
+}
+
+impl NodeLogEvent {
+  /// Parses a single stdout line of the node; returns `None` for lines of no interest.
+  pub fn parse(line: &str) -> Option<Self> {
+    let (level, message) = split_level(line)?;
+
+    if level == "ERROR" {
+      return Some(NodeLogEvent::Error { message: message.to_string() });
+    }
+
+    if let Some(rest) = message.strip_prefix("IBD: Processed ") {
+      let stage = if rest.contains("header") { IbdStage::Headers } else { IbdStage::Blocks };
+      return Some(NodeLogEvent::Ibd { stage, progress: percentage(rest) });
+    }
+
+    if message.starts_with("Processed ") && message.contains(" blocks and ") {
+      let mut numbers = message.split_whitespace().filter_map(|word| word.parse::<u64>().ok());
+      let blocks = numbers.next()?;
+      let headers = numbers.next()?;
+      return Some(NodeLogEvent::Processed { blocks, headers });
+    }
+
+    if message.contains("IBD") || message.contains("UTXO set") || message.contains("trusted") || message.contains("pruning point proof") {
+      if let Some(stage) = ibd_stage(message) {
+        return Some(NodeLogEvent::Ibd { stage, progress: percentage(message) });
+      }
+    }
+
+    if message.contains("UPnP") {
+      let lower = message.to_lowercase();
+      let success = !(lower.contains("fail") || lower.contains("error") || lower.contains("not found") || lower.contains("no gateway"));
+      return Some(NodeLogEvent::Upnp { success, message: message.to_string() });
+    }
+
+    if message.starts_with("P2P") || message.contains("peer") {
+      let address = message.split_whitespace().find(|word| word.parse::<std::net::SocketAddr>().is_ok()).map(|word| word.to_string());
+      if let Some(address) = address {
+        let lower = message.to_lowercase();
+        if lower.contains("disconnected") {
+          return Some(NodeLogEvent::PeerDisconnected { address });
+        } else if lower.contains("connected") {
+          return Some(NodeLogEvent::PeerConnected { address });
+        }
+      }
+    }
+
+    None
+  }
+}
+
+// Log lines look like `2024-01-01 12:00:00.000+00:00 [INFO ] message`
+fn split_level(line: &str) -> Option<(&str, &str)> {
+  let start = line.find('[')?;
+  let end = start + line[start..].find(']')?;
+  let level = line[start + 1..end].trim();
+  let message = line[end + 1..].trim();
+  Some((level, message))
+}
+
+// whole or decimal percentage such as `12%` or `12.5%`, rounded down so that 100 means done
+fn percentage(text: &str) -> Option<u8> {
+  let end = text.find("%)").or_else(|| text.find('%'))?;
+  let start = text[..end].rfind(|c: char| !(c.is_ascii_digit() || c == '.')).map(|idx| idx + 1).unwrap_or(0);
+  let value = text[start..end].parse::<f64>().ok().filter(|value| value.is_finite())?;
+  Some(value.clamp(0.0, 100.0) as u8)
+}
+
+fn ibd_stage(message: &str) -> Option<IbdStage> {
+  let lower = message.to_lowercase();
+  if lower.contains("completed successfully") || lower.contains("ibd finished") {
+    Some(IbdStage::Completed)
+  } else if lower.contains("proof") {
+    Some(IbdStage::Proof)
+  } else if lower.contains("trusted") || lower.contains("trust data") {
+    Some(IbdStage::TrustSync)
+  } else if lower.contains("utxo set") || lower.contains("utxo chunk") {
+    Some(IbdStage::UtxoSet)
+  } else if lower.contains("started") || lower.contains("starting ibd") {
+    Some(IbdStage::Started)
+  } else {
+    None
+  }
+}
+
+/// Sync progress of the integrated node, derived from [`NodeLogEvent`]s
+#[derive(Default, Debug, Clone)]
+pub struct SyncProgress {
+  pub stage: Option<IbdStage>,
+  pub progress: Option<u8>,
+  pub blocks_processed: u64,
+  pub headers_processed: u64,
+  pub connected_peers: usize,
+  pub upnp: Option<(bool, String)>,
+  pub last_error: Option<String>,
+  stage_start: Option<(Instant, u8)>,
+  eta: Option<Duration>,
+}
+
+impl SyncProgress {
+  pub fn update(&mut self, event: NodeLogEvent) {
+    match event {
+      NodeLogEvent::Ibd { stage, progress } => {
+        if self.stage != Some(stage) {
+          self.stage = Some(stage);
+          self.stage_start = progress.map(|progress| (Instant::now(), progress));
+          self.eta = None;
+        }
+        self.progress = progress;
+
+        if let Some(progress) = progress {
+          match self.stage_start {
+            Some((start, start_progress)) if progress > start_progress => {
+              let elapsed = start.elapsed().as_secs_f64();
+              let rate = elapsed / (progress - start_progress) as f64;
+              self.eta = Some(Duration::from_secs_f64(rate * (100 - progress) as f64));
+            }
+            None => self.stage_start = Some((Instant::now(), progress)),
+            _ => {}
+          }
+        }
+
+        if stage == IbdStage::Completed {
+          self.progress = Some(100);
+          self.eta = None;
+        }
+      }
+      NodeLogEvent::Processed { blocks, headers } => {
+        self.blocks_processed += blocks;
+        self.headers_processed += headers;
+      }
+      NodeLogEvent::PeerConnected { .. } => {
+        self.connected_peers += 1;
+      }
+      NodeLogEvent::PeerDisconnected { .. } => {
+        self.connected_peers = self.connected_peers.saturating_sub(1);
+      }
+      NodeLogEvent::Upnp { success, message } => {
+        self.upnp = Some((success, message));
+      }
+      NodeLogEvent::Error { message } => {
+        self.last_error = Some(message);
+      }
+    }
+  }
+
+  /// Returns true while an IBD stage with a known percentage is in progress
+  pub fn is_syncing(&self) -> bool {
+    matches!(self.stage, Some(stage) if stage != IbdStage::Completed) && self.progress.is_some()
+  }
+
+  pub fn fraction(&self) -> f32 {
+    self.progress.unwrap_or(0) as f32 / 100.0
+  }
+
+  pub fn eta(&self) -> Option<Duration> {
+    self.eta
+  }
+
+  pub fn describe(&self) -> Option<String> {
+    let stage = self.stage?;
+    let mut text = match self.progress {
+      Some(progress) => format!("{} {}%", stage, progress),
+      None => stage.to_string(),
+    };
+    if let Some(eta) = self.eta {
+      text.push_str(&format!(" - {} {}", i18n("ETA"), format_duration(eta)));
+    }
+    Some(text)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // stdout line as written by the waglaylad logger
+  fn line(level: &str, message: &str) -> String {
+    format!("2024-06-11 14:02:37.418+02:00 [{level:<5}] {message}")
+  }
+
+  #[test]
+  fn percentage_accepts_whole_and_decimal_values() {
+    assert_eq!(percentage("IBD: Processed 1200 blocks (12%)"), Some(12));
+    assert_eq!(percentage("IBD: Processed 1250 blocks (12.5%)"), Some(12));
+    assert_eq!(percentage("99.99% done"), Some(99));
+    assert_eq!(percentage("(100.0%)"), Some(100));
+    assert_eq!(percentage("(.5%)"), Some(0));
+    assert_eq!(percentage("no percentage here"), None);
+    assert_eq!(percentage("(%)"), None);
+  }
+
+  #[test]
+  fn split_level_reads_padded_levels() {
+    let line = line("INFO", "Processed 10 blocks and 10 headers in the last 10.00s");
+    assert_eq!(split_level(&line), Some(("INFO", "Processed 10 blocks and 10 headers in the last 10.00s")));
+    assert_eq!(split_level("no level"), None);
+  }
+
+  #[test]
+  fn parses_ibd_progress() {
+    let event = NodeLogEvent::parse(&line("INFO", "IBD: Processed 151204 block headers (42%) last block timestamp: 2024-06-11 13:58:02.000"));
+    assert_eq!(event, Some(NodeLogEvent::Ibd { stage: IbdStage::Headers, progress: Some(42) }));
+
+    let event = NodeLogEvent::parse(&line("INFO", "IBD: Processed 6300 blocks (87.5%) last block timestamp: 2024-06-11 13:59:40.000"));
+    assert_eq!(event, Some(NodeLogEvent::Ibd { stage: IbdStage::Blocks, progress: Some(87) }));
+  }
+
+  #[test]
+  fn parses_ibd_stages() {
+    let cases = [
+      ("Starting IBD with headers proof with peer 203.0.113.7:13111", IbdStage::Proof),
+      ("IBD started with peer 203.0.113.7:13111", IbdStage::Started),
+      ("Validating level 0 from the pruning point proof", IbdStage::Proof),
+      ("Applying the trusted data", IbdStage::TrustSync),
+      ("Received 2500 UTXO set chunks so far, totaling in 2500000 UTXOs", IbdStage::UtxoSet),
+      ("IBD with headers proof from 203.0.113.7:13111 completed successfully", IbdStage::Completed),
+    ];
+    for (message, stage) in cases {
+      match NodeLogEvent::parse(&line("INFO", message)) {
+        Some(NodeLogEvent::Ibd { stage: parsed, .. }) => assert_eq!(parsed, stage, "{message}"),
+        other => panic!("{message}: {other:?}"),
+      }
+    }
+  }
+
+  #[test]
+  fn parses_processed_counters() {
+    let event = NodeLogEvent::parse(&line(
+      "INFO",
+      "Processed 163 blocks and 164 headers in the last 10.01s (1204 transactions; 160 UTXO-validated blocks; 1.94 parents; 2.31 mergeset; 7.39 TPB; 12.8 mass)",
+    ));
+    assert_eq!(event, Some(NodeLogEvent::Processed { blocks: 163, headers: 164 }));
+  }
+
+  #[test]
+  fn parses_peer_events() {
+    let event = NodeLogEvent::parse(&line("INFO", "P2P Connected to outgoing peer 203.0.113.7:13111"));
+    assert_eq!(event, Some(NodeLogEvent::PeerConnected { address: "203.0.113.7:13111".to_string() }));
+
+    let event = NodeLogEvent::parse(&line("INFO", "P2P Disconnected from peer 203.0.113.7:13111"));
+    assert_eq!(event, Some(NodeLogEvent::PeerDisconnected { address: "203.0.113.7:13111".to_string() }));
+
+    // peer lines without an address are not counted
+    assert_eq!(NodeLogEvent::parse(&line("INFO", "P2P Connected to 3 peers")), None);
+  }
+
+  #[test]
+  fn parses_upnp_and_errors() {
+    let event = NodeLogEvent::parse(&line("INFO", "UPnP: registered external address 198.51.100.2:13111"));
+    assert!(matches!(event, Some(NodeLogEvent::Upnp { success: true, .. })));
+
+    let event = NodeLogEvent::parse(&line("WARN", "UPnP: no gateway found on the local network"));
+    assert!(matches!(event, Some(NodeLogEvent::Upnp { success: false, .. })));
+
+    let event = NodeLogEvent::parse(&line("ERROR", "IBD with peer 203.0.113.7:13111 failed: timeout"));
+    assert_eq!(event, Some(NodeLogEvent::Error { message: "IBD with peer 203.0.113.7:13111 failed: timeout".to_string() }));
+  }
+
+  #[test]
+  fn ignores_unrelated_lines() {
+    assert_eq!(NodeLogEvent::parse(&line("INFO", "Accepted block 4f1b2c via relay")), None);
+    assert_eq!(NodeLogEvent::parse("plain text without a level"), None);
+  }
+
+  #[test]
+  fn sync_progress_tracks_events() {
+    let mut progress = SyncProgress::default();
+    progress.update(NodeLogEvent::Ibd { stage: IbdStage::Headers, progress: Some(10) });
+    assert!(progress.is_syncing());
+    assert_eq!(progress.describe().as_deref(), Some(format!("{} 10%", IbdStage::Headers).as_str()));
+
+    progress.update(NodeLogEvent::PeerConnected { address: "203.0.113.7:13111".to_string() });
+    progress.update(NodeLogEvent::PeerDisconnected { address: "203.0.113.7:13111".to_string() });
+    progress.update(NodeLogEvent::PeerDisconnected { address: "203.0.113.7:13111".to_string() });
+    assert_eq!(progress.connected_peers, 0);
+
+    progress.update(NodeLogEvent::Ibd { stage: IbdStage::Completed, progress: None });
+    assert!(!progress.is_syncing());
+    assert_eq!(progress.progress, Some(100));
+  }
+}
@@ -1,6 +1,8 @@
 use waglayla_consensus_core::network::NetworkId;
 use waglayla_metrics_core::MetricsSnapshot;
 use waglayla_wallet_core::events::SyncState;
+use crate::node_log::SyncProgress;
+use crate::dx_manager::services::{BridgeMetricsReport, BridgeNodeStatus, DiscoveryStatus, FailoverReport, HealthState, ReconfigureStatus};
 
 #[derive(Default, Clone)]
 pub struct NodeState {
@@ -24,6 +26,23 @@ pub struct NodeState {
   pub difficulty: Option<u64>,
 
   pub error: Option<String>,
+
+  pub daemon_crashes: u32,
+  pub daemon_exit_status: Option<String>,
+  pub daemon_restart_pending: bool,
+  pub daemon_halted: bool,
+  pub daemon_stopping: bool,
+  pub daemon_version: Option<String>,
+  pub disk_space_low: bool,
+  pub disk_space_halted: bool,
+
+  pub sync_progress: SyncProgress,
+  pub health: HealthState,
+  pub remote_endpoints: Option<Box<FailoverReport>>,
+  pub discovery: Option<DiscoveryStatus>,
+  pub bridge_metrics: Option<Box<BridgeMetricsReport>>,
+  pub bridge_reconfigure: Option<ReconfigureStatus>,
+  pub bridge_node: Option<BridgeNodeStatus>,
 }
 
 impl NodeState {
@@ -100,4 +119,64 @@ impl NodeState {
   pub fn mempool_size(&self) -> Option<usize> {
     self.node_mempool_size
   }
+
+  pub fn daemon_crashes(&self) -> u32 {
+    self.daemon_crashes
+  }
+
+  pub fn daemon_exit_status(&self) -> &Option<String> {
+    &self.daemon_exit_status
+  }
+
+  pub fn is_daemon_restart_pending(&self) -> bool {
+    self.daemon_restart_pending
+  }
+
+  pub fn is_daemon_halted(&self) -> bool {
+    self.daemon_halted
+  }
+
+  pub fn is_daemon_stopping(&self) -> bool {
+    self.daemon_stopping
+  }
+
+  pub fn remote_endpoints(&self) -> Option<&FailoverReport> {
+    self.remote_endpoints.as_deref()
+  }
+
+  pub fn bridge_metrics(&self) -> Option<&BridgeMetricsReport> {
+    self.bridge_metrics.as_deref()
+  }
+
+  pub fn bridge_reconfigure(&self) -> Option<&ReconfigureStatus> {
+    self.bridge_reconfigure.as_ref()
+  }
+
+  pub fn bridge_node(&self) -> Option<&BridgeNodeStatus> {
+    self.bridge_node.as_ref()
+  }
+
+  pub fn discovery(&self) -> Option<&DiscoveryStatus> {
+    self.discovery.as_ref()
+  }
+
+  pub fn health(&self) -> HealthState {
+    self.health
+  }
+
+  pub fn is_disk_space_low(&self) -> bool {
+    self.disk_space_low
+  }
+
+  pub fn is_disk_space_halted(&self) -> bool {
+    self.disk_space_halted
+  }
+
+  pub fn daemon_version(&self) -> &Option<String> {
+    &self.daemon_version
+  }
+
+  pub fn sync_progress(&self) -> &SyncProgress {
+    &self.sync_progress
+  }
 }
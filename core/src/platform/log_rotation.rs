@@ -0,0 +1,256 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use crate::settings::LogSettings;
+
+/// Log file owned by this process, rotated by size and age on write
+pub struct RotatingLogFile {
+    path: PathBuf,
+    file: Option<File>,
+    size: u64,
+    opened: SystemTime,
+    policy: LogSettings,
+    compression: Option<JoinHandle<()>>,
+}
+
+impl RotatingLogFile {
+    pub fn open(path: PathBuf, policy: LogSettings) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = open_append(&path)?;
+        let metadata = file.metadata()?;
+        // an existing log keeps aging across application restarts
+        let opened = metadata.created().or_else(|_| metadata.modified()).unwrap_or_else(|_| SystemTime::now());
+
+        Ok(Self {
+            path,
+            file: Some(file),
+            size: metadata.len(),
+            opened,
+            policy,
+            compression: None,
+        })
+    }
+
+    pub fn set_policy(&mut self, policy: LogSettings) {
+        self.policy = policy;
+    }
+
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if needs_rotation(self.size, self.opened, &self.policy) {
+            self.rotate()?;
+        }
+
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => self.file.insert(open_append(&self.path)?),
+        };
+        file.write_all(line.as_bytes())?;
+        file.write_all(b"\n")?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        // the handle must be closed before the file can be renamed on Windows
+        self.file.take();
+
+        finish_compression(&mut self.compression);
+        let rotated = shift(&self.path, self.policy.retention)?;
+        match rotated {
+            Some(rotated) => {
+                fs::rename(&self.path, &rotated)?;
+                if self.policy.compress {
+                    self.compression = Some(compress_in_background(rotated));
+                }
+            }
+            None => fs::remove_file(&self.path)?,
+        }
+
+        self.file = Some(open_append(&self.path)?);
+        self.size = 0;
+        self.opened = SystemTime::now();
+        Ok(())
+    }
+}
+
+// touched on every rotation of an external log, whose truncation keeps the file creation time
+const ROTATION_MARKER_EXTENSION: &str = "rotated";
+
+/// Log file appended to by another process (the stratum bridge). The process
+/// keeps its handle open, so the file is rotated by copying and truncating it.
+pub struct ExternalLogFile {
+    path: PathBuf,
+    // start of the current log contents, read from disk once the log exists
+    opened: Option<SystemTime>,
+    compression: Option<JoinHandle<()>>,
+}
+
+impl ExternalLogFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, opened: None, compression: None }
+    }
+
+    fn marker(&self) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{ROTATION_MARKER_EXTENSION}"));
+        PathBuf::from(name)
+    }
+
+    /// The contents date from the last rotation, or from the creation of the log if it was
+    /// never rotated, so the age survives application restarts.
+    fn started(&self, metadata: &fs::Metadata) -> SystemTime {
+        fs::metadata(self.marker())
+            .and_then(|marker| marker.modified())
+            .or_else(|_| metadata.created())
+            .unwrap_or_else(|_| SystemTime::now())
+    }
+
+    /// Rotates the log if it exceeds the policy limits; returns true if a rotation took place.
+    pub fn check(&mut self, policy: &LogSettings) -> io::Result<bool> {
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        let size = metadata.len();
+        let opened = match self.opened {
+            Some(opened) => opened,
+            None => {
+                let opened = self.started(&metadata);
+                self.opened = Some(opened);
+                opened
+            }
+        };
+
+        if size == 0 || !needs_rotation(size, opened, policy) {
+            return Ok(false);
+        }
+
+        finish_compression(&mut self.compression);
+        if let Some(rotated) = shift(&self.path, policy.retention)? {
+            fs::copy(&self.path, &rotated)?;
+            if policy.compress {
+                self.compression = Some(compress_in_background(rotated));
+            }
+        }
+
+        // the writer opened the file in append mode, so it continues at the new end
+        OpenOptions::new().write(true).open(&self.path)?.set_len(0)?;
+        // recreated rather than truncated so that its modification time is reset everywhere
+        remove_if_exists(&self.marker())?;
+        File::create(self.marker())?;
+        self.opened = Some(SystemTime::now());
+        Ok(true)
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+}
+
+fn needs_rotation(size: u64, opened: SystemTime, policy: &LogSettings) -> bool {
+    let max_size = policy.max_size_mb.saturating_mul(1024 * 1024);
+    let max_age = Duration::from_secs(policy.max_age_hours.saturating_mul(3600));
+    let age = opened.elapsed().unwrap_or_default();
+
+    (max_size > 0 && size >= max_size) || (!max_age.is_zero() && size > 0 && age >= max_age)
+}
+
+fn rotated_path(path: &Path, index: u32, compressed: bool) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    if compressed {
+        name.push(".gz");
+    }
+    PathBuf::from(name)
+}
+
+/// Drops the oldest rotated file and shifts the rest up by one, returning
+/// the path for the newest rotated file or `None` if nothing is retained.
+fn shift(path: &Path, retention: u32) -> io::Result<Option<PathBuf>> {
+    // the policy may have been lowered since the last rotation
+    let mut index = retention.max(1);
+    while rotated_path(path, index, false).exists() || rotated_path(path, index, true).exists() {
+        for compressed in [false, true] {
+            remove_if_exists(&rotated_path(path, index, compressed))?;
+        }
+        index += 1;
+    }
+
+    if retention == 0 {
+        return Ok(None);
+    }
+
+    for index in (1..retention).rev() {
+        for compressed in [false, true] {
+            let from = rotated_path(path, index, compressed);
+            if from.exists() {
+                fs::rename(&from, rotated_path(path, index + 1, compressed))?;
+            }
+        }
+    }
+
+    Ok(Some(rotated_path(path, 1, false)))
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn compress_in_background(path: PathBuf) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        if let Err(err) = compress(&path) {
+            eprintln!("Failed to compress log file {}: {}", path.display(), err);
+        }
+    })
+}
+
+/// Waits for the compression of the previous rotation, which works on `<log>.1`
+/// and must be done before the next rotation shifts that file away.
+fn finish_compression(compression: &mut Option<JoinHandle<()>>) {
+    if let Some(compression) = compression.take() {
+        let _ = compression.join();
+    }
+}
+
+fn suffixed(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn compress(path: &Path) -> io::Result<()> {
+    static TEMP_ID: AtomicU64 = AtomicU64::new(0);
+
+    // written under a unique name and renamed into place once complete,
+    // so an interrupted compression never leaves a truncated archive behind
+    let temp = suffixed(path, &format!(".gz.{}-{}.tmp", std::process::id(), TEMP_ID.fetch_add(1, Ordering::Relaxed)));
+    if let Err(err) = write_compressed(path, &temp) {
+        let _ = fs::remove_file(&temp);
+        return Err(err);
+    }
+
+    fs::rename(&temp, suffixed(path, ".gz"))?;
+    fs::remove_file(path)
+}
+
+fn write_compressed(source: &Path, target: &Path) -> io::Result<()> {
+    let mut source = File::open(source)?;
+    let mut encoder = GzEncoder::new(File::create(target)?, Compression::default());
+    io::copy(&mut source, &mut encoder)?;
+    encoder.finish()?.sync_all()
+}
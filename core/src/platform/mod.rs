@@ -1 +1,2 @@
-pub mod paths;
\ No newline at end of file
+pub mod paths;
+pub mod log_rotation;
\ No newline at end of file
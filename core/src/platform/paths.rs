@@ -1,8 +1,9 @@
 use std::path::PathBuf;
-use std::fs::{self, File, OpenOptions};
 use std::io;
 use std::env;
 use workflow_core::dirs;
+use crate::settings::LogSettings;
+use super::log_rotation::RotatingLogFile;
 
 /// Get appropriate log file path based on platform
 pub fn get_log_file_path(service_name: &str) -> PathBuf {
@@ -13,7 +14,7 @@ pub fn get_log_file_path(service_name: &str) -> PathBuf {
             if let Some(home) = dirs::home_dir() {
                 let log_dir = home.join("Library/Logs/com.example.wala-wagdx");
                 // Try to create the directory
-                let _ = fs::create_dir_all(&log_dir);
+                let _ = std::fs::create_dir_all(&log_dir);
                 return log_dir.join(format!("{}.log", service_name));
             }
         }
@@ -45,18 +46,7 @@ pub fn is_app_bundle() -> bool {
     false
 }
 
-/// Open a log file with proper error handling
-pub fn open_log_file(service_name: &str) -> io::Result<File> {
-    let path = get_log_file_path(service_name);
-    
-    // Create parent directory if needed
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-    
-    OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(true)
-        .open(&path)
+/// Open a log file that is rotated according to the given policy
+pub fn open_log_file(service_name: &str, policy: LogSettings) -> io::Result<RotatingLogFile> {
+    RotatingLogFile::open(get_log_file_path(service_name), policy)
 }
\ No newline at end of file
@@ -8,7 +8,7 @@ use workflow_core::{runtime, task::spawn};
 use sys_locale::get_locale;
 use serde_json::Value;
 
-use std::net::IpAddr;
+use std::net::{Ipv4Addr, SocketAddr};
 use std::time::Duration;
 
 const SETTINGS_REVISION: &str = "0.0.0";
@@ -23,12 +23,16 @@ cfg_if! {
       Remote,
       #[default]
       IntegratedAsDaemon,
+      IntegratedInProc,
+      ExternalAsDaemon,
     }
 
-    const WAGLAYLAD_NODE_KINDS: [WagLayladNodeKind; 3] = [
+    const WAGLAYLAD_NODE_KINDS: [WagLayladNodeKind; 5] = [
       WagLayladNodeKind::Disabled,
       WagLayladNodeKind::Remote,
       WagLayladNodeKind::IntegratedAsDaemon,
+      WagLayladNodeKind::IntegratedInProc,
+      WagLayladNodeKind::ExternalAsDaemon,
     ];
 
     impl std::fmt::Display for WagLayladNodeKind {
@@ -37,6 +41,8 @@ cfg_if! {
           WagLayladNodeKind::Disabled => write!(f, "{}", i18n("Disabled")),
           WagLayladNodeKind::Remote => write!(f, "{}", i18n("Light")),
           WagLayladNodeKind::IntegratedAsDaemon => write!(f, "{}", i18n("Full")),
+          WagLayladNodeKind::IntegratedInProc => write!(f, "{}", i18n("Embedded")),
+          WagLayladNodeKind::ExternalAsDaemon => write!(f, "{}", i18n("External")),
         }
       }
     }
@@ -75,6 +81,10 @@ impl WagLayladNodeKind {
       WagLayladNodeKind::Remote => i18n("Connects to a Remote WagLayla Rusty Node via wRPC."),
       #[cfg(not(target_arch = "wasm32"))]
       WagLayladNodeKind::IntegratedAsDaemon => i18n("The node is spawned as a child daemon process (recommended, if you have the ample free storage available)."),
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladNodeKind::IntegratedInProc => i18n("The node runs inside this application, without a separate process or a local RPC port. Use this if spawning processes or opening local ports is restricted."),
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladNodeKind::ExternalAsDaemon => i18n("A user-supplied waglaylad binary is spawned as a child daemon process."),
     }
   }
 
@@ -85,6 +95,10 @@ impl WagLayladNodeKind {
       WagLayladNodeKind::Remote => false,
       #[cfg(not(target_arch = "wasm32"))]
       WagLayladNodeKind::IntegratedAsDaemon => true,
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladNodeKind::IntegratedInProc => true,
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladNodeKind::ExternalAsDaemon => true,
     }
   }
 
@@ -95,6 +109,10 @@ impl WagLayladNodeKind {
       WagLayladNodeKind::Remote => false,
       #[cfg(not(target_arch = "wasm32"))]
       WagLayladNodeKind::IntegratedAsDaemon => true,
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladNodeKind::IntegratedInProc => true,
+      #[cfg(not(target_arch = "wasm32"))]
+      WagLayladNodeKind::ExternalAsDaemon => true,
     }
   }
 }
@@ -125,6 +143,10 @@ pub enum RpcConfig {
     url: Option<String>,
     encoding: WrpcEncoding,
     resolver_urls: Option<Vec<Arc<String>>>,
+    #[serde(default)]
+    tls: TlsTrust,
+    #[serde(default)]
+    proxy: Option<ProxySettings>,
   },
 }
 
@@ -142,6 +164,8 @@ impl Default for RpcConfig {
       url: Some(url.to_string()),
       encoding: WrpcEncoding::Borsh,
       resolver_urls: None,
+      tls: TlsTrust::default(),
+      proxy: None,
     }
   }
 }
@@ -164,6 +188,97 @@ impl RpcConfig {
           RpcConfig::Wrpc { resolver_urls, .. } => resolver_urls.clone(),
       }
   }
+
+  pub fn tls(&self) -> TlsTrust {
+      match self {
+          RpcConfig::Wrpc { tls, .. } => tls.clone(),
+      }
+  }
+
+  pub fn proxy(&self) -> Option<ProxySettings> {
+      match self {
+          RpcConfig::Wrpc { proxy, .. } => proxy.clone(),
+      }
+  }
+}
+
+// SOCKS5 proxy for connections to remote nodes, e.g. a local Tor client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ProxySettings {
+  pub enable: bool,
+  pub address: String,               // host:port of the SOCKS5 proxy
+  pub remote_dns: bool,              // host names are resolved by the proxy instead of locally
+  pub username: String,              // optional, enables username/password authentication
+  pub password: String,
+}
+
+impl Default for ProxySettings {
+  fn default() -> Self {
+    ProxySettings {
+      enable: false,
+      address: "127.0.0.1:9050".to_string(),
+      remote_dns: true,
+      username: String::default(),
+      password: String::default(),
+    }
+  }
+}
+
+impl ProxySettings {
+  pub fn validate(&self) -> std::result::Result<(), &'static str> {
+    let address = self.address.trim();
+    match address.rsplit_once(':') {
+      Some((host, port)) if !host.is_empty() && port.parse::<u16>().map(|port| port > 0).unwrap_or(false) => {}
+      _ => return Err(i18n("The proxy address must be in the host:port format")),
+    }
+    if self.username.len() > 255 || self.password.len() > 255 {
+      return Err(i18n("The proxy username and password are limited to 255 bytes"));
+    }
+    Ok(())
+  }
+}
+
+// Trust configuration for wss:// endpoints signed by a private CA or identified by a pinned certificate
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct TlsTrust {
+  pub ca_file: String,               // PEM file with an additional trusted CA certificate
+  pub fingerprint: String,           // SHA-256 fingerprint of the expected server certificate
+}
+
+impl TlsTrust {
+  /// `true` if the endpoint can not be verified against the system certificates alone
+  pub fn is_custom(&self) -> bool {
+    !self.ca_file.trim().is_empty() || !self.fingerprint.trim().is_empty()
+  }
+
+  /// Parses the pinned fingerprint; accepts hex digits with optional `:` or space separators
+  pub fn fingerprint(&self) -> Result<Option<[u8; 32]>> {
+    let hex = self.fingerprint
+      .chars()
+      .filter(|c| !matches!(c, ':' | ' '))
+      .collect::<String>();
+    if hex.is_empty() {
+      return Ok(None);
+    }
+    // `from_str_radix` alone would also take a sign, as in `+f`
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+      return Err(Error::InvalidTlsFingerprint);
+    }
+
+    let mut fingerprint = [0u8; 32];
+    for (byte, digits) in fingerprint.iter_mut().zip(hex.as_bytes().chunks(2)) {
+      let digits = std::str::from_utf8(digits).map_err(|_| Error::InvalidTlsFingerprint)?;
+      *byte = u8::from_str_radix(digits, 16).map_err(|_| Error::InvalidTlsFingerprint)?;
+    }
+    Ok(Some(fingerprint))
+  }
+}
+
+/// Formats a certificate fingerprint as colon separated hex pairs
+pub fn format_fingerprint(fingerprint: &[u8]) -> String {
+  fingerprint.iter().map(|byte| format!("{byte:02X}")).collect::<Vec<_>>().join(":")
 }
 
 // Endpoint interface parameters
@@ -217,6 +332,7 @@ pub enum NodeConnectionConfigKind {
   PublicServerRandom,
   PublicServerCustom,
   Custom,
+  Resolver,
   // Local,
 }
 
@@ -230,6 +346,7 @@ impl std::fmt::Display for NodeConnectionConfigKind {
         write!(f, "{}", i18n("Custom Public Node"))
       }
       NodeConnectionConfigKind::Custom => write!(f, "{}", i18n("Custom")),
+      NodeConnectionConfigKind::Resolver => write!(f, "{}", i18n("Resolver")),
       // NodeConnectionConfigKind::Local => write!(f, "{}", i18n("Local")),
     }
   }
@@ -241,6 +358,7 @@ impl NodeConnectionConfigKind {
       NodeConnectionConfigKind::PublicServerRandom,
       // NodeConnectionConfigKind::PublicServerCustom,
       NodeConnectionConfigKind::Custom,
+      NodeConnectionConfigKind::Resolver,
       // NodeConnectionConfigKind::Local,
     ]
     .iter()
@@ -251,55 +369,355 @@ impl NodeConnectionConfigKind {
       self,
       NodeConnectionConfigKind::PublicServerRandom
         | NodeConnectionConfigKind::PublicServerCustom
+        | NodeConnectionConfigKind::Resolver
     )
   }
 }
 
-// Leave the management to the WagLayla daemon
+// Default memory scale; leave the management to the WagLayla daemon
 pub const NODE_MEMORY_SCALE: f64 = 1.0;
+pub const NODE_OUTBOUND_TARGET: u16 = 8;
+pub const NODE_INBOUND_LIMIT: u16 = 128;
+pub const NODE_RPC_MAX_CLIENTS: u16 = 128;
+
+// Rough figures used to estimate the node footprint in the settings
+const NODE_BASE_MEMORY: u64 = 1_000_000_000;
+const NODE_CACHE_MEMORY: u64 = 6_000_000_000;
+const NODE_PEER_MEMORY: u64 = 8_000_000;
+const NODE_RPC_CLIENT_MEMORY: u64 = 2_000_000;
+const NODE_PRUNED_DISK: u64 = 50_000_000_000;
 
-// Stratum bridge configuration struct
+// Stratum bridge configuration struct, also written to the `config.yaml` read by the bridge
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct BridgeSettings {
-  pub stratum_port: String,          // e.g., ":5555"
+  #[serde(with = "listen_address")]
+  pub stratum_port: SocketAddr,      // e.g., ":5555"
   pub waglayla_address: String,      // e.g., "localhost:12110"
   pub min_share_diff: u64,           // Unsigned integer for difficulty
   pub var_diff: bool,                // Boolean for variable difficulty
   pub shares_per_min: u32,           // Shares per minute target
   pub var_diff_stats: bool,          // Boolean for stats logging
   pub solo_mining: bool,             // Boolean for solo mining mode
-  pub block_wait_time: String,     // e.g., "500ms" parsed as Duration
+  #[serde(with = "go_duration")]
+  pub block_wait_time: Duration,     // e.g., "500ms"
   pub extranonce_size: u8,           // 0-3 bytes
   pub print_stats: bool,             // Boolean for console stats
   pub log_to_file: bool,             // Boolean for file logging
-  pub prom_port: String,             // e.g., ":2114"
+  #[serde(with = "listen_address")]
+  pub prom_port: SocketAddr,         // e.g., ":2114"
 }
 
 impl Default for BridgeSettings {
   fn default() -> Self {
     BridgeSettings {
-      stratum_port: ":5555".to_string(),
+      stratum_port: SocketAddr::from((Ipv4Addr::UNSPECIFIED, 5555)),
       waglayla_address: "localhost:12110".to_string(),
       min_share_diff: 4,
       var_diff: true,
       shares_per_min: 4,
       var_diff_stats: false,
       solo_mining: false,
-      block_wait_time: "500ms".to_string(), // 500ms
+      block_wait_time: Duration::from_millis(500),
       extranonce_size: 0,
       print_stats: true,
       log_to_file: true,
-      prom_port: ":2114".to_string(),
+      prom_port: SocketAddr::from((Ipv4Addr::UNSPECIFIED, 2114)),
+    }
+  }
+}
+
+impl BridgeSettings {
+  /// Checks the settings for values the bridge would reject or listeners that would
+  /// fail to bind. `reserved` lists the listeners of the local node.
+  pub fn validate(&self, reserved: &[(&'static str, SocketAddr)]) -> std::result::Result<(), String> {
+    let overlaps = |a: &SocketAddr, b: &SocketAddr| {
+      a.port() == b.port() && (a.ip() == b.ip() || a.ip().is_unspecified() || b.ip().is_unspecified())
+    };
+
+    if self.stratum_port.port() == 0 || self.prom_port.port() == 0 {
+      return Err(i18n("The stratum and Prometheus ports must not be 0").to_string());
+    }
+    if overlaps(&self.stratum_port, &self.prom_port) {
+      return Err(i18n("The stratum and Prometheus ports must be different").to_string());
+    }
+    for (name, address) in reserved {
+      for (label, listener) in [(i18n("stratum"), &self.stratum_port), (i18n("Prometheus"), &self.prom_port)] {
+        if overlaps(listener, address) {
+          return Err(format!("{} {} {} {} {}", i18n("The"), label, i18n("port is already used by the node"), name, i18n("listener")));
+        }
+      }
+    }
+
+    match self.waglayla_address.trim().rsplit_once(':') {
+      Some((host, port)) if !host.is_empty() && port.parse::<u16>().map(|port| port > 0).unwrap_or(false) => {}
+      _ => return Err(i18n("The WagLayla address must be in the host:port format").to_string()),
+    }
+    if self.extranonce_size > 3 {
+      return Err(i18n("The extranonce size must be between 0 and 3 bytes").to_string());
+    }
+    if self.min_share_diff == 0 {
+      return Err(i18n("The minimum share difficulty must be at least 1").to_string());
+    }
+    if self.var_diff && self.shares_per_min == 0 {
+      return Err(i18n("Vardiff requires a target of at least 1 share per minute").to_string());
+    }
+    if self.block_wait_time.is_zero() {
+      return Err(i18n("The block wait time must be greater than 0").to_string());
+    }
+    Ok(())
+  }
+
+  /// True when `waglayla_address` points at this machine, which only serves a local node
+  pub fn targets_loopback(&self) -> bool {
+    let host = self.waglayla_address.trim().rsplit_once(':').map(|(host, _)| host).unwrap_or_default();
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    host.eq_ignore_ascii_case("localhost") || host.parse::<std::net::IpAddr>().map(|ip| ip.is_loopback()).unwrap_or(false)
+  }
+
+  /// `config.yaml` contents for the bridge, `None` if the settings are not valid
+  pub fn to_yaml(&self) -> Option<String> {
+    if let Err(err) = self.validate(&[]) {
+      log_error!("refusing to write an invalid bridge config: {err}");
+      return None;
+    }
+    match serde_yaml::to_string(self) {
+      Ok(yaml) => Some(yaml),
+      Err(err) => {
+        log_error!("unable to serialize the bridge config: {err}");
+        None
+      }
+    }
+  }
+}
+
+/// Parses a bridge listen address; a bare ":port" listens on all interfaces
+pub fn parse_listen_address(address: &str) -> std::result::Result<SocketAddr, String> {
+  let address = address.trim();
+  let parsed = match address.strip_prefix(':') {
+    Some(port) => port.parse::<u16>().map(|port| SocketAddr::from((Ipv4Addr::UNSPECIFIED, port))).ok(),
+    None => address.parse::<SocketAddr>().ok(),
+  };
+  parsed.ok_or_else(|| format!("{} '{}' ({})", i18n("Invalid listen address"), address, i18n("expected :port or ip:port")))
+}
+
+/// Formats a listen address the way the bridge expects it
+pub fn format_listen_address(address: &SocketAddr) -> String {
+  if address.ip() == Ipv4Addr::UNSPECIFIED {
+    format!(":{}", address.port())
+  } else {
+    address.to_string()
+  }
+}
+
+mod listen_address {
+  use super::*;
+
+  pub fn serialize<S: serde::Serializer>(address: &SocketAddr, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format_listen_address(address))
+  }
+
+  pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<SocketAddr, D::Error> {
+    let address = String::deserialize(deserializer)?;
+    parse_listen_address(&address).map_err(serde::de::Error::custom)
+  }
+}
+
+/// Parses a duration in the format of Go's `time.ParseDuration`, e.g. "500ms" or "1m30s".
+/// Durations beyond the range of Go's `time.Duration` are refused.
+pub fn parse_go_duration(duration: &str) -> std::result::Result<Duration, String> {
+  let error = || format!("{} '{}' ({})", i18n("Invalid duration"), duration.trim(), i18n("expected e.g. 500ms, 2s or 1m30s"));
+  let mut rest = duration.trim();
+  if rest == "0" {
+    return Ok(Duration::ZERO);
+  }
+  if rest.is_empty() {
+    return Err(error());
+  }
+
+  let mut total = 0f64;
+  while !rest.is_empty() {
+    let number_len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).ok_or_else(error)?;
+    let value = rest[..number_len].parse::<f64>().map_err(|_| error())?;
+    rest = &rest[number_len..];
+    let unit_len = rest.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(rest.len());
+    let scale = match &rest[..unit_len] {
+      "ns" => 1e-9,
+      "us" | "\u{b5}s" | "\u{3bc}s" => 1e-6,
+      "ms" => 1e-3,
+      "s" => 1.0,
+      "m" => 60.0,
+      "h" => 3600.0,
+      _ => return Err(error()),
+    };
+    total += value * scale;
+    rest = &rest[unit_len..];
+  }
+  // edited on every frame, so input of any size must not panic
+  Duration::try_from_secs_f64(total)
+    .ok()
+    .filter(|duration| duration.as_nanos() <= i64::MAX as u128)
+    .ok_or_else(error)
+}
+
+/// Formats a duration so that Go's `time.ParseDuration` reads it back unchanged
+pub fn format_go_duration(duration: &Duration) -> String {
+  let nanos = duration.as_nanos();
+  if nanos == 0 {
+    "0s".to_string()
+  } else if nanos % 1_000_000_000 == 0 {
+    format!("{}s", nanos / 1_000_000_000)
+  } else if nanos % 1_000_000 == 0 {
+    format!("{}ms", nanos / 1_000_000)
+  } else if nanos % 1_000 == 0 {
+    format!("{}us", nanos / 1_000)
+  } else {
+    format!("{nanos}ns")
+  }
+}
+
+mod go_duration {
+  use super::*;
+
+  pub fn serialize<S: serde::Serializer>(duration: &Duration, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format_go_duration(duration))
+  }
+
+  pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Duration, D::Error> {
+    let duration = String::deserialize(deserializer)?;
+    parse_go_duration(&duration).map_err(serde::de::Error::custom)
+  }
+}
+
+/// Falls back to the default bridge settings when the stored ones can not be
+/// read, rather than discarding all settings
+fn deserialize_bridge_settings<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<BridgeSettings, D::Error> {
+  let value = Value::deserialize(deserializer)?;
+  Ok(serde_json::from_value(value).unwrap_or_else(|err| {
+    log_warn!("invalid bridge settings, using defaults: {err}");
+    BridgeSettings::default()
+  }))
+}
+
+// Rotation policy for the node service and bridge log files
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LogSettings {
+  pub max_size_mb: u64,              // rotate once the active log exceeds this size
+  pub max_age_hours: u64,            // rotate once the active log is older than this
+  pub retention: u32,                // number of rotated files to keep
+  pub compress: bool,                // gzip rotated files
+}
+
+impl Default for LogSettings {
+  fn default() -> Self {
+    LogSettings {
+      max_size_mb: 50,
+      max_age_hours: 24 * 7,
+      retention: 5,
+      compress: true,
+    }
+  }
+}
+
+// Action taken by the health monitor when the node stalls
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HealthRemediation {
+  Notify,
+  #[default]
+  ReconnectRpc,
+  RestartNode,
+  FallbackRemote,
+}
+
+impl std::fmt::Display for HealthRemediation {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      HealthRemediation::Notify => write!(f, "{}", i18n("Notify Only")),
+      HealthRemediation::ReconnectRpc => write!(f, "{}", i18n("Reconnect RPC")),
+      HealthRemediation::RestartNode => write!(f, "{}", i18n("Restart Node")),
+      HealthRemediation::FallbackRemote => write!(f, "{}", i18n("Switch to Fallback Node")),
+    }
+  }
+}
+
+impl HealthRemediation {
+  pub fn iter() -> impl Iterator<Item = &'static HealthRemediation> {
+    [
+      HealthRemediation::Notify,
+      HealthRemediation::ReconnectRpc,
+      HealthRemediation::RestartNode,
+      HealthRemediation::FallbackRemote,
+    ]
+    .iter()
+  }
+}
+
+// Stall detection thresholds and remediation policy of the node health monitor
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct HealthSettings {
+  pub enable: bool,
+  pub stall_timeout_minutes: u64,    // no DAA, header or block progress for this long
+  pub no_peers_timeout_minutes: u64, // no connected peers for this long
+  pub remediation: HealthRemediation,
+  pub max_remediations: u32,         // attempts before the monitor only reports
+  pub fallback_url: String,          // wRPC url used by HealthRemediation::FallbackRemote
+}
+
+impl Default for HealthSettings {
+  fn default() -> Self {
+    HealthSettings {
+      enable: true,
+      stall_timeout_minutes: 10,
+      no_peers_timeout_minutes: 5,
+      remediation: HealthRemediation::default(),
+      max_remediations: 3,
+      fallback_url: String::default(),
     }
   }
 }
 
+impl HealthSettings {
+  /// Node settings connecting to the fallback node, or `None` if no
+  /// fallback is configured or it is already in use
+  pub fn fallback_node_settings(&self, node: &NodeSettings) -> Option<NodeSettings> {
+    let url = self.fallback_url.trim();
+    let in_use = node.node_kind == WagLayladNodeKind::Remote
+      && node.connection_config_kind == NodeConnectionConfigKind::Custom
+      && node.wrpc_url == url;
+    if url.is_empty() || in_use {
+      return None;
+    }
+
+    let mut fallback = node.clone();
+    fallback.node_kind = WagLayladNodeKind::Remote;
+    fallback.connection_config_kind = NodeConnectionConfigKind::Custom;
+    fallback.wrpc_url = url.to_string();
+    Some(fallback)
+  }
+}
+
 // Complete settings suite/section for the daemon
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case", default)]
 pub struct NodeSettings {
+  pub network: Network,
   pub connection_config_kind: NodeConnectionConfigKind,
   pub wrpc_url: String,
+  // additional remote endpoints tried in order when `wrpc_url` is unusable
+  pub wrpc_fallback_urls: Vec<String>,
+  // resolver services used by NodeConnectionConfigKind::Resolver; empty selects the default resolvers
+  pub resolver_urls: Vec<String>,
+  // PEM file with a CA certificate trusted for wss:// custom endpoints
+  pub wrpc_tls_ca_file: String,
+  // SHA-256 fingerprint the wss:// custom endpoint certificate must match
+  pub wrpc_tls_fingerprint: String,
+  // proxy for all remote node connections, including public node discovery
+  pub proxy: ProxySettings,
+  // custom remote nodes use `wrpc_proxy` instead of the global `proxy`
+  pub wrpc_proxy_override: bool,
+  pub wrpc_proxy: ProxySettings,
   #[serde(default)]
   pub enable_wrpc_borsh: bool,
   #[serde(default)]
@@ -321,13 +739,31 @@ pub struct NodeSettings {
   pub waglaylad_daemon_storage_folder_enable: bool,
   #[serde(default)]
   pub waglaylad_daemon_storage_folder: String,
+  pub waglaylad_daemon_restart_enable: bool,
+  pub waglaylad_daemon_restart_limit: u32,
+  pub waglaylad_daemon_shutdown_timeout: u64,
+  pub disk_space_warning_gb: u64,
+  pub disk_space_stop_gb: u64,
+  pub ram_scale: f64,
+  pub outbound_target: u16,
+  pub inbound_limit: u16,
+  pub rpc_max_clients: u16,
+  pub archival: bool,
 }
 
 impl Default for NodeSettings {
   fn default() -> Self {
     Self {
+      network: Network::default(),
       connection_config_kind: NodeConnectionConfigKind::default(),
       wrpc_url: "127.0.0.1".to_string(),
+      wrpc_fallback_urls: Vec::new(),
+      resolver_urls: Vec::new(),
+      wrpc_tls_ca_file: String::default(),
+      wrpc_tls_fingerprint: String::default(),
+      proxy: ProxySettings::default(),
+      wrpc_proxy_override: false,
+      wrpc_proxy: ProxySettings::default(),
       wrpc_encoding: WrpcEncoding::Borsh,
       enable_wrpc_borsh: true,
       wrpc_borsh_network_interface: NetworkInterfaceConfig::default(),
@@ -344,16 +780,77 @@ impl Default for NodeSettings {
       waglaylad_daemon_args_enable: false,
       waglaylad_daemon_storage_folder_enable: false,
       waglaylad_daemon_storage_folder: String::default(),
+      waglaylad_daemon_restart_enable: true,
+      waglaylad_daemon_restart_limit: 5,
+      waglaylad_daemon_shutdown_timeout: 30,
+      disk_space_warning_gb: 20,
+      disk_space_stop_gb: 5,
+      ram_scale: NODE_MEMORY_SCALE,
+      outbound_target: NODE_OUTBOUND_TARGET,
+      inbound_limit: NODE_INBOUND_LIMIT,
+      rpc_max_clients: NODE_RPC_MAX_CLIENTS,
+      archival: false,
     }
   }
 }
 
 impl NodeSettings {
+  pub fn uses_resolver(&self) -> bool {
+    self.node_kind == WagLayladNodeKind::Remote && self.connection_config_kind == NodeConnectionConfigKind::Resolver
+  }
+
+  pub fn tls_trust(&self) -> TlsTrust {
+    TlsTrust {
+      ca_file: self.wrpc_tls_ca_file.trim().to_string(),
+      fingerprint: self.wrpc_tls_fingerprint.trim().to_string(),
+    }
+  }
+
+  /// Proxy for public node discovery and public connections
+  pub fn public_proxy(&self) -> Option<ProxySettings> {
+    self.proxy.enable.then(|| self.proxy.clone())
+  }
+
+  /// Proxy for the configured remote connection, `None` for a direct connection
+  pub fn remote_proxy(&self) -> Option<ProxySettings> {
+    if self.connection_config_kind == NodeConnectionConfigKind::Custom && self.wrpc_proxy_override {
+      self.wrpc_proxy.enable.then(|| self.wrpc_proxy.clone())
+    } else {
+      self.public_proxy()
+    }
+  }
+
+  /// Ordered list of custom remote endpoints, starting with the primary `wrpc_url`
+  pub fn remote_endpoints(&self) -> Vec<String> {
+    let mut endpoints: Vec<String> = Vec::new();
+    for url in std::iter::once(&self.wrpc_url).chain(self.wrpc_fallback_urls.iter()) {
+      let url = url.trim();
+      if !url.is_empty() && !endpoints.iter().any(|endpoint| endpoint == url) {
+        endpoints.push(url.to_string());
+      }
+    }
+    endpoints
+  }
+
+  /// Rough resident memory of a local node with the current resource settings
+  pub fn estimated_memory_usage(&self) -> u64 {
+    let peers = self.outbound_target as u64 + self.inbound_limit as u64;
+    NODE_BASE_MEMORY
+      + (NODE_CACHE_MEMORY as f64 * self.ram_scale) as u64
+      + peers * NODE_PEER_MEMORY
+      + self.rpc_max_clients as u64 * NODE_RPC_CLIENT_MEMORY
+  }
+
+  /// Rough disk usage of a pruned node; `None` for archival nodes, which grow without bound
+  pub fn estimated_disk_usage(&self) -> Option<u64> {
+    (!self.archival).then_some(NODE_PRUNED_DISK)
+  }
+
   cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
       #[allow(clippy::if_same_then_else)]
       pub fn compare(&self, other: &NodeSettings) -> Option<bool> {
-        if self.node_kind != other.node_kind {
+        if self.network != other.network || self.node_kind != other.node_kind {
           Some(true)
         } else if self.connection_config_kind != other.connection_config_kind
         {
@@ -364,8 +861,12 @@ impl NodeSettings {
           Some(true)
         } else if self.enable_grpc != other.enable_grpc
           || self.grpc_network_interface != other.grpc_network_interface
+          || self.enable_wrpc_borsh != other.enable_wrpc_borsh
+          || self.wrpc_borsh_network_interface != other.wrpc_borsh_network_interface
           || self.wrpc_url != other.wrpc_url
           || self.wrpc_encoding != other.wrpc_encoding
+          || self.wrpc_tls_ca_file != other.wrpc_tls_ca_file
+          || self.wrpc_tls_fingerprint != other.wrpc_tls_fingerprint
           || self.enable_wrpc_json != other.enable_wrpc_json
           || self.wrpc_json_network_interface != other.wrpc_json_network_interface
           || self.enable_upnp != other.enable_upnp
@@ -374,8 +875,30 @@ impl NodeSettings {
           Some(true)
         } else if self.waglaylad_daemon_args != other.waglaylad_daemon_args
           || self.waglaylad_daemon_args_enable != other.waglaylad_daemon_args_enable
+          || self.ram_scale != other.ram_scale
+          || self.outbound_target != other.outbound_target
+          || self.inbound_limit != other.inbound_limit
+          || self.rpc_max_clients != other.rpc_max_clients
+          || self.archival != other.archival
         {
           Some(self.node_kind.is_config_capable())
+        } else if self.resolver_urls != other.resolver_urls {
+          Some(self.uses_resolver())
+        } else if self.proxy != other.proxy
+          || self.wrpc_proxy_override != other.wrpc_proxy_override
+          || self.wrpc_proxy != other.wrpc_proxy
+        {
+          Some(self.node_kind == WagLayladNodeKind::Remote)
+        } else if self.waglaylad_daemon_binary != other.waglaylad_daemon_binary {
+          Some(self.node_kind == WagLayladNodeKind::ExternalAsDaemon)
+        } else if self.waglaylad_daemon_restart_enable != other.waglaylad_daemon_restart_enable
+          || self.waglaylad_daemon_restart_limit != other.waglaylad_daemon_restart_limit
+          || self.waglaylad_daemon_shutdown_timeout != other.waglaylad_daemon_shutdown_timeout
+          || self.disk_space_warning_gb != other.disk_space_warning_gb
+          || self.disk_space_stop_gb != other.disk_space_stop_gb
+          || self.wrpc_fallback_urls != other.wrpc_fallback_urls
+        {
+          Some(false)
         } else {
           None
         }
@@ -392,6 +915,15 @@ impl NodeSettings {
             return Some(true);
         } else if self.wrpc_url != other.wrpc_url
           || self.wrpc_encoding != other.wrpc_encoding
+          || self.wrpc_tls_ca_file != other.wrpc_tls_ca_file
+          || self.wrpc_tls_fingerprint != other.wrpc_tls_fingerprint
+        {
+          Some(true)
+        } else if self.resolver_urls != other.resolver_urls {
+          Some(self.uses_resolver())
+        } else if self.proxy != other.proxy
+          || self.wrpc_proxy_override != other.wrpc_proxy_override
+          || self.wrpc_proxy != other.wrpc_proxy
         {
           Some(true)
         } else {
@@ -407,12 +939,29 @@ impl RpcConfig {
   pub fn from_node_settings(settings: &NodeSettings, _options: Option<RpcOptions>) -> Self {
     match settings.connection_config_kind {
       NodeConnectionConfigKind::PublicServerRandom => {
+        // native builds discover public nodes through the NodeDiscoveryService,
+        // elsewhere the default resolvers select one
         RpcConfig::Wrpc {
-          url: Some(get_public_node(10, 13110)
-            .unwrap_or(IpAddr::from_str("127.0.0.1").unwrap())
-            .to_string()),
-          encoding: settings.wrpc_encoding,
+          url: None,
+          encoding: WrpcEncoding::Borsh,
           resolver_urls: None,
+          tls: TlsTrust::default(),
+          proxy: settings.public_proxy(),
+        }
+      }
+      NodeConnectionConfigKind::Resolver => {
+        let resolver_urls = settings.resolver_urls
+          .iter()
+          .map(|url| url.trim())
+          .filter(|url| !url.is_empty())
+          .map(|url| Arc::new(url.to_string()))
+          .collect::<Vec<_>>();
+        RpcConfig::Wrpc {
+          url: None,
+          encoding: settings.wrpc_encoding,
+          resolver_urls: (!resolver_urls.is_empty()).then_some(resolver_urls),
+          tls: TlsTrust::default(),
+          proxy: settings.remote_proxy(),
         }
       }
       _ => {
@@ -420,6 +969,8 @@ impl RpcConfig {
           url: Some(settings.wrpc_url.clone()),
           encoding: settings.wrpc_encoding,
           resolver_urls: None,
+          tls: settings.tls_trust(),
+          proxy: settings.remote_proxy(),
         }
       }
     }
@@ -475,8 +1026,12 @@ pub struct Settings {
   pub update: String,
   #[serde(default)]
   pub node: NodeSettings,
-  #[serde(default)]
+  #[serde(default, deserialize_with = "deserialize_bridge_settings")]
   pub bridge: BridgeSettings,
+  #[serde(default)]
+  pub logs: LogSettings,
+  #[serde(default)]
+  pub health: HealthSettings,
   pub user_interface: UserInterfaceSettings,
   pub language_code: String,
   pub update_monitor: bool,
@@ -532,6 +1087,8 @@ impl Default for Settings {
       update: crate::app::VERSION.to_string(),
       node: NodeSettings::default(),
       bridge: BridgeSettings::default(),
+      logs: LogSettings::default(),
+      health: HealthSettings::default(),
       user_interface: UserInterfaceSettings::default(),
       language_code,
       update_monitor: true,
@@ -555,8 +1112,9 @@ impl Settings {
     bridge_cfg_storage.ensure_dir().await?;
     // println!("{}", storage.filename().display());
     workflow_store::fs::write_json(storage.filename(), self).await?;
-    let yaml = serde_yaml::to_string(&self.bridge).expect("Bridge Config Error");
-    workflow_store::fs::write_string(&bridge_cfg_storage.filename(), &yaml).await?;
+    if let Some(yaml) = self.bridge.to_yaml() {
+      workflow_store::fs::write_string(bridge_cfg_storage.filename(), &yaml).await?;
+    }
     Ok(())
   }
 
@@ -570,9 +1128,10 @@ impl Settings {
         if let Err(err) = workflow_store::fs::write_json(storage.filename(), &this).await {
           log_error!("Settings::store_sync() error: {}", err);
         }
-        let yaml = serde_yaml::to_string(&this.bridge).expect("Bridge Config Error");
-        if let Err(err) = workflow_store::fs::write_string(bridge_cfg_storage.filename(), &yaml).await {
-          log_error!("Settings::store_sync() error: {}", err);
+        if let Some(yaml) = this.bridge.to_yaml() {
+          if let Err(err) = workflow_store::fs::write_string(bridge_cfg_storage.filename(), &yaml).await {
+            log_error!("Settings::store_sync() error: {}", err);
+          }
         }
       });
     } else {
@@ -580,8 +1139,9 @@ impl Settings {
       bridge_cfg_storage.ensure_dir_sync()?;
       // println!("{}", storage.filename().display());
       workflow_store::fs::write_json_sync(storage.filename(), self)?;
-      let yaml = serde_yaml::to_string(&self.bridge).expect("Bridge Config Error");
-      workflow_store::fs::write_string_sync(bridge_cfg_storage.filename(), &yaml)?;
+      if let Some(yaml) = self.bridge.to_yaml() {
+        workflow_store::fs::write_string_sync(bridge_cfg_storage.filename(), &yaml)?;
+      }
     }
     Ok(self)
   }
@@ -594,9 +1154,10 @@ impl Settings {
 
     if !bridge_cfg_storage.exists().await.unwrap_or(false) {
       let def = Self::default();
-      let yaml = serde_yaml::to_string(&def.bridge).expect("Bridge Config Error");
-      if let Err(err) = workflow_store::fs::write_string(bridge_cfg_storage.filename(), &yaml).await {
-        log_error!("Settings::store_sync() error: {}", err);
+      if let Some(yaml) = def.bridge.to_yaml() {
+        if let Err(err) = workflow_store::fs::write_string(bridge_cfg_storage.filename(), &yaml).await {
+          log_error!("Settings::store_sync() error: {}", err);
+        }
       }
     }
 
@@ -614,6 +1175,14 @@ impl Settings {
               NodeConnectionConfigKind::PublicServerRandom;
             }
 
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+              let migrated = crate::dx_manager::services::waglayla::config::migrate_custom_args(&mut settings.node);
+              if !migrated.is_empty() {
+                log_info!("Moved custom waglaylad arguments into the node settings: {}", migrated.join(" "));
+              }
+            }
+
             Ok(settings)
           }
         }
@@ -634,3 +1203,89 @@ impl Settings {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_go_duration_reads_go_formats() {
+    assert_eq!(parse_go_duration("500ms"), Ok(Duration::from_millis(500)));
+    assert_eq!(parse_go_duration("1m30s"), Ok(Duration::from_secs(90)));
+    assert_eq!(parse_go_duration(" 2s "), Ok(Duration::from_secs(2)));
+    assert_eq!(parse_go_duration("1.5h"), Ok(Duration::from_secs(5400)));
+    assert_eq!(parse_go_duration("250us"), Ok(Duration::from_micros(250)));
+    assert_eq!(parse_go_duration("0"), Ok(Duration::ZERO));
+    assert_eq!(parse_go_duration("0s"), Ok(Duration::ZERO));
+  }
+
+  #[test]
+  fn parse_go_duration_rejects_invalid_input() {
+    for input in ["", "abc", "10", "5 s", "1d", "s", "1..5s", "-1s", "1m30"] {
+      assert!(parse_go_duration(input).is_err(), "{input}");
+    }
+  }
+
+  #[test]
+  fn parse_go_duration_rejects_overflow() {
+    assert!(parse_go_duration("99999999999999999999h").is_err());
+    assert!(parse_go_duration(&format!("{}h", "9".repeat(400))).is_err());
+    // Go durations are limited to about 292 years
+    assert!(parse_go_duration("2562047h").is_ok());
+    assert!(parse_go_duration("2562048h").is_err());
+  }
+
+  #[test]
+  fn go_duration_round_trips() {
+    for duration in [Duration::ZERO, Duration::from_millis(500), Duration::from_secs(90), Duration::from_micros(1500), Duration::from_nanos(7)] {
+      assert_eq!(parse_go_duration(&format_go_duration(&duration)), Ok(duration));
+    }
+  }
+
+  fn trust(fingerprint: &str) -> TlsTrust {
+    TlsTrust { fingerprint: fingerprint.to_string(), ..Default::default() }
+  }
+
+  #[test]
+  fn tls_fingerprint_accepts_separators() {
+    let expected: [u8; 32] = std::array::from_fn(|index| (index as u8) * 8 + 1);
+    let formatted = format_fingerprint(&expected);
+    assert_eq!(trust(&formatted).fingerprint().unwrap(), Some(expected));
+    assert_eq!(trust(&formatted.replace(':', "").to_lowercase()).fingerprint().unwrap(), Some(expected));
+    assert_eq!(trust(&formatted.replace(':', " ")).fingerprint().unwrap(), Some(expected));
+    assert_eq!(trust("").fingerprint().unwrap(), None);
+    assert_eq!(trust(" : ").fingerprint().unwrap(), None);
+  }
+
+  #[test]
+  fn tls_fingerprint_rejects_invalid_input() {
+    let valid = "AB".repeat(32);
+    for fingerprint in [
+      valid[..62].to_string(),
+      format!("{valid}AB"),
+      format!("{}ZZ", &valid[..62]),
+      format!("{}+F", &valid[..62]),
+      format!("{}é", &valid[..62]),
+      format!("{}\tA", &valid[..62]),
+    ] {
+      assert!(trust(&fingerprint).fingerprint().is_err(), "{fingerprint}");
+    }
+  }
+
+  #[test]
+  fn listen_address_round_trips() {
+    for address in [":5555", "127.0.0.1:5555", "192.168.1.10:2114", "[::1]:5555", "[::]:2114"] {
+      let parsed = parse_listen_address(address).unwrap();
+      assert_eq!(format_listen_address(&parsed), address);
+    }
+    assert_eq!(parse_listen_address(":5555"), Ok(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 5555))));
+    assert_eq!(format_listen_address(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, 2114))), ":2114");
+  }
+
+  #[test]
+  fn parse_listen_address_rejects_invalid_input() {
+    for input in ["", ":", "5555", ":99999", "localhost:5555", "127.0.0.1", ":port"] {
+      assert!(parse_listen_address(input).is_err(), "{input}");
+    }
+  }
+}
@@ -3,12 +3,76 @@ use crate::error::Error;
 use std::sync::{Arc, Mutex};
 use walkdir::WalkDir;
 
+// free space kept on the target volume on top of the migrated data
+const MIGRATION_SPACE_MARGIN: u64 = 1024 * 1024 * 1024;
+const MIGRATION_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
 #[derive(PartialEq, Eq)]
 pub struct StorageFolder {
   pub path: PathBuf,
   pub folder_size: u64,
   pub folder_size_string: String,
   pub confirm_deletion: bool,
+  pub migration_target: String,
+  pub migration_remove_source: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationStage {
+  Stopping,
+  Copying,
+  Verifying,
+  RemovingSource,
+  Done,
+  Failed(String),
+}
+
+impl std::fmt::Display for MigrationStage {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      MigrationStage::Stopping => write!(f, "{}", i18n("Stopping node...")),
+      MigrationStage::Copying => write!(f, "{}", i18n("Copying data...")),
+      MigrationStage::Verifying => write!(f, "{}", i18n("Verifying copy...")),
+      MigrationStage::RemovingSource => write!(f, "{}", i18n("Removing original data...")),
+      MigrationStage::Done => write!(f, "{}", i18n("Migration complete")),
+      MigrationStage::Failed(err) => write!(f, "{}: {}", i18n("Migration failed"), err),
+    }
+  }
+}
+
+/// Space on the volume holding the storage root
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+  pub available: u64,
+  pub total: u64,
+}
+
+/// Progress of a data folder migration to a new storage root
+#[derive(Debug, Clone)]
+pub struct StorageMigration {
+  pub source: PathBuf,
+  pub target: PathBuf,
+  pub target_root: PathBuf,
+  pub remove_source: bool,
+  pub stage: MigrationStage,
+  pub copied: u64,
+  pub total: u64,
+  // set on completion when part of the source folder could not be removed
+  pub leftover: Option<String>,
+}
+
+impl StorageMigration {
+  pub fn is_finished(&self) -> bool {
+    matches!(self.stage, MigrationStage::Done | MigrationStage::Failed(_))
+  }
+
+  pub fn fraction(&self) -> f32 {
+    if self.total == 0 {
+      0.0
+    } else {
+      self.copied as f32 / self.total as f32
+    }
+  }
 }
 
 #[derive(Default, Debug, Clone)]
@@ -33,6 +97,9 @@ impl StorageUpdateOptions {
 pub struct Storage {
   pub folder: Arc<Mutex<Option<StorageFolder>>>,
   pub storage_root: Arc<Mutex<Option<PathBuf>>>,
+  pub network: Arc<Mutex<Network>>,
+  pub migration: Arc<Mutex<Option<StorageMigration>>>,
+  pub disk: Arc<Mutex<Option<DiskSpace>>>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -41,9 +108,21 @@ impl Storage {
     Self {
       folder: Arc::new(Mutex::new(None)),
       storage_root: Arc::new(Mutex::new(None)),
+      network: Arc::new(Mutex::new(Network::default())),
+      migration: Arc::new(Mutex::new(None)),
+      disk: Arc::new(Mutex::new(None)),
     }
   }
 
+  pub fn track_network(&self, network: Network) {
+    *self.network.lock().unwrap() = network;
+    self.update();
+  }
+
+  pub fn network(&self) -> Network {
+    *self.network.lock().unwrap()
+  }
+
   pub fn track_storage_root(&self, storage_root: Option<&str>) {
     *self.storage_root.lock().unwrap() = storage_root.map(PathBuf::from);
     self.update();
@@ -54,7 +133,34 @@ impl Storage {
       .lock()
       .unwrap()
       .clone()
-      .unwrap_or_else(|| PathBuf::from("./app_data")) // Default path
+      .unwrap_or_else(waglaylad_lib::daemon::get_app_dir)
+  }
+
+  /// Refreshes the free space next to the data directory
+  pub fn update_disk_space(&self) -> Option<DiskSpace> {
+    let disk = disk_space(&self.storage_root());
+    *self.disk.lock().unwrap() = disk;
+    disk
+  }
+
+  pub fn disk_space(&self) -> Option<DiskSpace> {
+    *self.disk.lock().unwrap()
+  }
+
+  pub fn folder_size(&self) -> Option<u64> {
+    self.folder.lock().unwrap().as_ref().map(|folder| folder.folder_size)
+  }
+
+  pub fn migration(&self) -> Option<StorageMigration> {
+    self.migration.lock().unwrap().clone()
+  }
+
+  pub fn set_migration(&self, migration: Option<StorageMigration>) {
+    *self.migration.lock().unwrap() = migration;
+  }
+
+  pub fn is_migrating(&self) -> bool {
+    self.migration.lock().unwrap().as_ref().map(|migration| !migration.is_finished()).unwrap_or(false)
   }
 
   pub fn update(&self) -> Result<()> {
@@ -63,32 +169,34 @@ impl Storage {
       return Err(Error::Custom("Storage root does not exist".to_string()));
     }
 
-    let path = app_dir.join("data-mainnet");
+    let path = app_dir.join(self.network().data_folder());
     if path.exists() && path.is_dir() {
-      let mut folder_size = 0;
-      for entry in WalkDir::new(&path).into_iter().flatten() {
-        folder_size += entry
-          .metadata()
-          .map(|metadata| metadata.len())
-          .unwrap_or_default();
-      }
-
-      self.update_folder_size(folder_size, path);
+      self.update_folder_size(folder_size(&path), path);
       Ok(())
     } else {
+      self.folder.lock().unwrap().take();
       Err(Error::Custom("Data directory not found".to_string()))
     }
   }
 
   fn update_folder_size(&self, folder_size: u64, path: PathBuf) {
-    let folder_size_string = format!("{:.2} MB", folder_size as f64 / 1_000_000.0);
+    let folder_size_string = format_size(folder_size);
     
     let mut folder_lock = self.folder.lock().unwrap();
+    // keep the pending user input of the settings panel across periodic updates
+    let (migration_target, migration_remove_source) = folder_lock
+      .take()
+      .filter(|folder| folder.path == path)
+      .map(|folder| (folder.migration_target, folder.migration_remove_source))
+      .unwrap_or_default();
+
     *folder_lock = Some(StorageFolder {
       path,
       folder_size,
       folder_size_string,
       confirm_deletion: false,
+      migration_target,
+      migration_remove_source,
     });
   }
 
@@ -145,6 +253,75 @@ impl Storage {
     }
   }
 
+  fn render_migration(
+    &self,
+    ui: &mut Ui,
+    source: &Path,
+    folder_size: u64,
+    migration_target: &mut String,
+    remove_source: &mut bool,
+  ) {
+    CollapsingHeader::new(i18n("Move Data Folder"))
+      .default_open(false)
+      .show(ui, |ui| {
+        if let Some(migration) = self.migration().filter(|migration| !migration.is_finished()) {
+          ui.label(format!("{} → {}", migration.source.display(), migration.target.display()));
+          ui.add(
+            egui::ProgressBar::new(migration.fraction())
+              .text(format!("{} {} / {}", migration.stage, format_size(migration.copied), format_size(migration.total)))
+          );
+          return;
+        }
+
+        ui.horizontal(|ui| {
+          ui.label(i18n("New storage folder:"));
+          ui.add(TextEdit::singleline(migration_target));
+        });
+        ui.checkbox(remove_source, i18n("Remove the original data after a verified copy"));
+
+        let target_root = PathBuf::from(migration_target.trim());
+        let target = target_root.join(self.network().data_folder());
+        let available = available_space(&target_root);
+
+        let error = if migration_target.trim().is_empty() {
+          Some(i18n("Please specify the new storage folder"))
+        } else if target_root == self.storage_root() {
+          Some(i18n("The data is already stored in this folder"))
+        } else if target.exists() && target.read_dir().map(|mut dir| dir.next().is_some()).unwrap_or(true) {
+          Some(i18n("The new storage folder already contains node data"))
+        } else if available.map(|available| available < folder_size + MIGRATION_SPACE_MARGIN).unwrap_or(false) {
+          Some(i18n("Not enough free space at the new storage folder"))
+        } else {
+          None
+        };
+
+        if let Some(available) = available {
+          ui.label(format!("{} {} / {} {}", i18n("Required:"), format_size(folder_size), i18n("Available:"), format_size(available)));
+        }
+
+        if let Some(error) = error {
+          ui.colored_label(theme_color().error_color, error);
+        } else {
+          ui.label(i18n("The node will be stopped during the migration and restarted from the new folder afterwards."));
+        }
+
+        if ui.medium_button_enabled(error.is_none(), i18n("Start Migration")).clicked() {
+          let migration = StorageMigration {
+            source: source.to_path_buf(),
+            target,
+            target_root,
+            remove_source: *remove_source,
+            stage: MigrationStage::Stopping,
+            copied: 0,
+            total: folder_size,
+            leftover: None,
+          };
+          self.set_migration(Some(migration.clone()));
+          manager().waglayla_service().migrate_storage(migration);
+        }
+      });
+  }
+
   pub fn render_settings(&self, core: &mut Core, ui: &mut Ui) {
     let mut node_folder = self.folder.lock().unwrap();
     if let Some(folder) = node_folder.as_mut() {
@@ -154,13 +331,15 @@ impl Storage {
         .show(ui, |ui| {
           ui.vertical(|ui| {
             let StorageFolder {
+              folder_size,
               folder_size_string,
               path,
               confirm_deletion,
-              ..
+              migration_target,
+              migration_remove_source,
             } = folder;
 
-            CollapsingHeader::new(format!("{}: {folder_size_string}", "MAINNET".to_string()))
+            CollapsingHeader::new(format!("{}: {folder_size_string}", self.network().name().to_uppercase()))
             .default_open(false)
             .show(ui, |ui| {
               let is_running = core.settings.node.node_kind.is_local();
@@ -171,7 +350,7 @@ impl Storage {
                     manager().error(format!("Error opening folder: {:?}", err));
                   }
                 }
-                if ui.medium_button_enabled(!is_running && !*confirm_deletion, i18n("Delete Data Folder")).clicked() {
+                if ui.medium_button_enabled(!is_running && !*confirm_deletion && !self.is_migrating(), i18n("Delete Data Folder")).clicked() {
                   *confirm_deletion = true;
                 }
               });
@@ -199,10 +378,189 @@ impl Storage {
                 }
                 ui.add_sized(vec2(260.,4.), Separator::default());
               }
+
+              self.render_migration(ui, path, *folder_size, migration_target, migration_remove_source);
             });
           });
         });
       });
     }
   }
-}
\ No newline at end of file
+}
+
+pub fn format_size(bytes: u64) -> String {
+  if bytes >= 1_000_000_000 {
+    format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
+  } else {
+    format!("{:.2} MB", bytes as f64 / 1_000_000.0)
+  }
+}
+
+fn folder_size(path: &Path) -> u64 {
+  WalkDir::new(path)
+    .into_iter()
+    .flatten()
+    .filter(|entry| entry.file_type().is_file())
+    .map(|entry| entry.metadata().map(|metadata| metadata.len()).unwrap_or_default())
+    .sum()
+}
+
+/// Returns the space available on the volume holding `path`, or on the
+/// volume of its closest existing ancestor if `path` does not exist yet.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn available_space(path: &Path) -> Option<u64> {
+  disk_space(path).map(|disk| disk.available)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn disk_space(path: &Path) -> Option<DiskSpace> {
+  use sysinfo::{DiskExt, System, SystemExt};
+
+  let path = path.ancestors().find(|path| path.exists())?.canonicalize().ok()?;
+
+  let mut system = System::new();
+  system.refresh_disks_list();
+  system
+    .disks()
+    .iter()
+    .filter(|disk| path.starts_with(disk.mount_point()))
+    .max_by_key(|disk| disk.mount_point().as_os_str().len())
+    .map(|disk| DiskSpace {
+      available: disk.available_space(),
+      total: disk.total_space(),
+    })
+}
+
+/// Copies (or moves) a node data folder to `target`, checking free space up front and
+/// verifying the SHA-256 digest of every copied file before removing the source.
+/// The caller reports [`MigrationStage::Done`]; once the copy is verified the migration
+/// succeeds, returning the reason if the source folder could not be removed entirely.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn migrate_folder(
+  source: &Path,
+  target: &Path,
+  remove_source: bool,
+  cancel: &AtomicBool,
+  progress: impl Fn(MigrationStage, u64, u64),
+) -> Result<Option<String>> {
+  let source = source.canonicalize()?;
+  if target.exists() && target.read_dir()?.next().is_some() {
+    return Err(Error::StorageMigration(format!("{} is not empty", target.display())));
+  }
+  if let Some(parent) = target.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  let target_parent = target.parent().map(|parent| parent.canonicalize()).transpose()?;
+  if target_parent.map(|parent| parent.starts_with(&source)).unwrap_or(false) {
+    return Err(Error::StorageMigration(i18n("the new location is inside the current data folder").to_string()));
+  }
+
+  let total = folder_size(&source);
+  progress(MigrationStage::Copying, 0, total);
+
+  // a rename on the same volume moves the data without copying it
+  if remove_source && std::fs::rename(&source, target).is_ok() {
+    return Ok(None);
+  }
+
+  let available = available_space(target).unwrap_or(u64::MAX);
+  if available < total.saturating_add(MIGRATION_SPACE_MARGIN) {
+    return Err(Error::InsufficientDiskSpace { required: total + MIGRATION_SPACE_MARGIN, available });
+  }
+
+  let copied = copy_and_verify(&source, target, total, cancel, &progress).map_err(|err| {
+    // the target was empty before, so a partial copy can be discarded entirely
+    std::fs::remove_dir_all(target).ok();
+    err
+  })?;
+
+  if remove_source {
+    progress(MigrationStage::RemovingSource, copied, total);
+    // the verified copy is complete, so the node must switch to it even if the source is half removed
+    if let Err(err) = std::fs::remove_dir_all(&source) {
+      log_warn!("unable to remove the migrated data folder {}: {err}", source.display());
+      return Ok(Some(format!("{} {}: {}", i18n("Part of the old data folder was left behind at"), source.display(), err)));
+    }
+  }
+
+  Ok(None)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn copy_and_verify(
+  source: &Path,
+  target: &Path,
+  total: u64,
+  cancel: &AtomicBool,
+  progress: &impl Fn(MigrationStage, u64, u64),
+) -> Result<u64> {
+  use sha2::{Digest, Sha256};
+
+  let mut copied = 0;
+  let mut last_report = Instant::now();
+  let mut buffer = vec![0u8; 1024 * 1024];
+  // digests of the source files as they were read, checked against the copies below
+  let mut digests = Vec::new();
+  for entry in WalkDir::new(source) {
+    let entry = entry.map_err(|err| Error::StorageMigration(err.to_string()))?;
+    let relative = entry.path().strip_prefix(source).expect("walkdir yields paths below its root");
+    let destination = target.join(relative);
+
+    if entry.file_type().is_dir() {
+      std::fs::create_dir_all(&destination)?;
+      continue;
+    }
+
+    let mut reader = std::fs::File::open(entry.path())?;
+    let mut writer = std::fs::File::create(&destination)?;
+    let mut hasher = Sha256::new();
+    loop {
+      if cancel.load(Ordering::Relaxed) {
+        return Err(Error::StorageMigration(i18n("cancelled").to_string()));
+      }
+      let read = std::io::Read::read(&mut reader, &mut buffer)?;
+      if read == 0 {
+        break;
+      }
+      hasher.update(&buffer[..read]);
+      std::io::Write::write_all(&mut writer, &buffer[..read])?;
+      copied += read as u64;
+
+      if last_report.elapsed() > MIGRATION_PROGRESS_INTERVAL {
+        last_report = Instant::now();
+        progress(MigrationStage::Copying, copied, total);
+      }
+    }
+    writer.sync_all()?;
+    digests.push((relative.to_path_buf(), hasher.finalize()));
+  }
+
+  // the copies are read back and hashed; the source is only removed if every digest matches
+  progress(MigrationStage::Verifying, 0, total);
+  let mut verified = 0;
+  for (relative, expected) in digests {
+    let mut reader = std::fs::File::open(target.join(&relative))?;
+    let mut hasher = Sha256::new();
+    loop {
+      if cancel.load(Ordering::Relaxed) {
+        return Err(Error::StorageMigration(i18n("cancelled").to_string()));
+      }
+      let read = std::io::Read::read(&mut reader, &mut buffer)?;
+      if read == 0 {
+        break;
+      }
+      hasher.update(&buffer[..read]);
+      verified += read as u64;
+
+      if last_report.elapsed() > MIGRATION_PROGRESS_INTERVAL {
+        last_report = Instant::now();
+        progress(MigrationStage::Verifying, verified, total);
+      }
+    }
+    if hasher.finalize() != expected {
+      return Err(Error::StorageMigration(format!("{} {}", i18n("verification failed for"), relative.display())));
+    }
+  }
+
+  Ok(copied)
+}
@@ -1,4 +1,6 @@
 use ahash::AHashSet;
+use crate::error::Error;
+use crate::result::Result;
 
 #[derive(Default)]
 pub struct Arglist {
@@ -22,3 +24,77 @@ impl From<Arglist> for Vec<String> {
         list
     }
 }
+
+/// Splits a command line string into individual arguments.
+/// Supports single quotes (literal) and double quotes (with `\"` and `\\` escapes),
+/// so values containing spaces can be passed as a single argument.
+pub fn split_args(input: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(Error::InvalidNodeArgs("unterminated single quote".to_string())),
+                    }
+                }
+            }
+            '"' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err(Error::InvalidNodeArgs("unterminated double quote".to_string())),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(Error::InvalidNodeArgs("unterminated double quote".to_string())),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_token {
+                    args.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            c => {
+                in_token = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_token {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+/// Joins arguments back into a command line string readable by [`split_args`],
+/// quoting arguments that are empty or contain whitespace, quotes or backslashes.
+pub fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if !arg.is_empty() && !arg.chars().any(|c| c.is_whitespace() || matches!(c, '\'' | '"' | '\\')) {
+                arg.clone()
+            } else {
+                format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
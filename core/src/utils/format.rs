@@ -173,6 +173,19 @@ pub fn format_hashrate(num: u64) -> String {
   format!("{}{}", whole_part_formatted, fractional_with_suffix)
 }
 
+pub fn format_duration(duration: Duration) -> String {
+  let secs = duration.as_secs();
+  let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+
+  if hours > 0 {
+    format!("{}h {:02}m", hours, minutes)
+  } else if minutes > 0 {
+    format!("{}m {:02}s", minutes, seconds)
+  } else {
+    format!("{}s", seconds)
+  }
+}
+
 pub fn format_balance_split_8(num: u64) -> (String, String) {
   let suffixes = ["", "K", "M", "B", "T", "Qa", "Qi", "Sx", "Sp", "Oc", "N", "Dc"];
   let mut value = num as f64;
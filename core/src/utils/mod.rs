@@ -10,8 +10,6 @@ mod arglist;
 pub use arglist::*;
 mod qr;
 pub use qr::*;
-mod get_public;
-pub use get_public::*;
 mod text;
 pub use text::*;
 mod dx_image;
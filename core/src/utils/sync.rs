@@ -1,7 +1,20 @@
 use super::*;
+use crate::dx_manager::services::HealthState;
 
 pub fn describe_sync(state: NodeState) -> ( String, Color32) {
-  if state.is_connected() {
+  if state.is_disk_space_halted() {
+    (i18n("Disk Full - Node Stopped").to_string(), theme_color().error_color)
+  } else if state.is_daemon_stopping() {
+    (i18n("Stopping Node...").to_string(), theme_color().warning_color)
+  } else if state.is_daemon_halted() {
+    (i18n("Node Crashed").to_string(), theme_color().error_color)
+  } else if state.is_daemon_restart_pending() && !state.is_connected() {
+    (i18n("Restarting Node...").to_string(), theme_color().warning_color)
+  } else if state.is_connected() && state.health().is_problem() {
+    (state.health().to_string(), theme_color().warning_color)
+  } else if let HealthState::Recovering(_) = state.health() {
+    (state.health().to_string(), theme_color().warning_color)
+  } else if state.is_connected() {
     match state.sync_state.unwrap() {
       SyncState::Headers { progress, .. } => {
         (format!("{} {}%", i18n("Syncing Headers..."), progress), theme_color().separator_color)
@@ -38,13 +51,17 @@ pub fn describe_sync(state: NodeState) -> ( String, Color32) {
         (i18n("Not Synced").to_string(), theme_color().separator_color)
       }
     }
+  } else if let Some(status) = state.discovery().filter(|status| status.is_active()) {
+    (status.to_string(), theme_color().separator_color)
   } else {
     (i18n("Not Connected").to_string(), theme_color().separator_color)
   }
 }
 
 pub fn connection_icon(state: NodeState) -> ( String, Color32 ) {
-  if state.is_connected() {
+  if state.is_connected() && state.health().is_problem() {
+    (egui_phosphor::bold::WARNING.to_string(), Color32::YELLOW)
+  } else if state.is_connected() {
     match state.sync_state.unwrap() {
       SyncState::UtxoResync { .. } |
       SyncState::Synced => {
@@ -67,4 +84,16 @@ pub fn describe_peers(state: NodeState) -> String {
 pub fn describe_daa(state: NodeState) -> String {
   let daa_score = state.current_daa_score().unwrap_or(0_u64);
   format!("{} {}", i18n("DAA:"), format_number(daa_score))
-}
\ No newline at end of file
+}
+pub fn describe_health(state: &NodeState) -> Option<String> {
+  state.health().describe()
+}
+
+pub fn describe_daemon_crashes(state: &NodeState) -> Option<String> {
+  if state.daemon_crashes() == 0 {
+    return None;
+  }
+
+  let status = state.daemon_exit_status().clone().unwrap_or_else(|| i18n("unknown").to_string());
+  Some(format!("{} {} ({} {})", i18n("Node crashes:"), state.daemon_crashes(), i18n("last exit:"), status))
+}